@@ -0,0 +1,414 @@
+use crate::config;
+use crate::errors::SavingsError;
+use crate::storage_types::{AssetHolding, DataKey};
+use crate::{flexi, goal, group, lock, strategy};
+use soroban_sdk::{panic_with_error, symbol_short, token, Address, Env, Vec};
+
+// ========== Allowlist Management ==========
+
+/// Adds an asset contract to the allowlist of assets users can save in.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `admin` - The admin calling this function
+/// * `asset` - The Stellar Asset Contract address to allow
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+pub fn add_supported_asset(env: &Env, admin: Address, asset: Address) -> Result<(), SavingsError> {
+    config::require_admin(env, &admin)?;
+    allow_asset(env, &asset);
+    Ok(())
+}
+
+/// Removes an asset contract from the allowlist.
+///
+/// Existing plans in the asset are unaffected and can still be withdrawn;
+/// only new deposits are rejected.
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+/// * `SavingsError::UnsupportedAsset` - If the asset is not on the allowlist
+pub fn remove_supported_asset(
+    env: &Env,
+    admin: Address,
+    asset: Address,
+) -> Result<(), SavingsError> {
+    config::require_admin(env, &admin)?;
+
+    let assets = get_supported_assets(env);
+    let index = assets
+        .first_index_of(&asset)
+        .ok_or(SavingsError::UnsupportedAsset)?;
+
+    let mut updated = assets;
+    updated.remove(index);
+    env.storage()
+        .instance()
+        .set(&DataKey::SupportedAssets, &updated);
+
+    env.events().publish((symbol_short!("asset_rm"),), asset);
+
+    Ok(())
+}
+
+/// Returns every asset currently accepted for new deposits.
+pub fn get_supported_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::SupportedAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Returns true if the asset is on the allowlist.
+pub fn is_supported_asset(env: &Env, asset: &Address) -> bool {
+    get_supported_assets(env).contains(asset)
+}
+
+/// Rejects deposits in assets that are not on the allowlist.
+///
+/// # Errors
+/// * `SavingsError::UnsupportedAsset` - If the asset is not allowed
+pub fn require_supported_asset(env: &Env, asset: &Address) -> Result<(), SavingsError> {
+    if !is_supported_asset(env, asset) {
+        return Err(SavingsError::UnsupportedAsset);
+    }
+    Ok(())
+}
+
+/// Appends `asset` to the allowlist if it is not already present.
+pub(crate) fn allow_asset(env: &Env, asset: &Address) {
+    let mut assets = get_supported_assets(env);
+    if assets.contains(asset) {
+        return;
+    }
+    assets.push_back(asset.clone());
+    env.storage()
+        .instance()
+        .set(&DataKey::SupportedAssets, &assets);
+
+    env.events()
+        .publish((symbol_short!("asset_add"),), asset.clone());
+}
+
+// ========== Custody Transfers ==========
+
+/// How a deposit's funds leave the depositor's wallet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Funding {
+    /// The depositor signed this invocation and transfers the funds directly
+    Signed,
+    /// The contract spends the depositor's token allowance, so no signature
+    /// is needed (used by AutoSave keepers)
+    Allowance,
+}
+
+/// Moves `amount` of `asset` from `from` into contract custody.
+pub fn pull(env: &Env, asset: &Address, from: &Address, amount: i128) {
+    if amount > 0 {
+        token::Client::new(env, asset).transfer(from, env.current_contract_address(), &amount);
+    }
+}
+
+/// Moves `amount` of `asset` from `from` into contract custody, either as a
+/// signed transfer or against the allowance `from` granted this contract.
+pub(crate) fn pull_funded(
+    env: &Env,
+    asset: &Address,
+    from: &Address,
+    amount: i128,
+    funding: Funding,
+) {
+    match funding {
+        Funding::Signed => pull(env, asset, from, amount),
+        Funding::Allowance => {
+            if amount > 0 {
+                let contract = env.current_contract_address();
+                token::Client::new(env, asset).transfer_from(&contract, from, &contract, &amount);
+            }
+        }
+    }
+}
+
+/// Pays `amount` of `asset` out of contract custody to `to`, recalling funds
+/// from the asset's yield strategy first if custody alone cannot cover it.
+pub fn pay(env: &Env, asset: &Address, to: &Address, amount: i128) {
+    if amount > 0 {
+        strategy::ensure_liquid(env, asset, amount).unwrap_or_else(|e| panic_with_error!(env, e));
+        token::Client::new(env, asset).transfer(&env.current_contract_address(), to, &amount);
+    }
+}
+
+// ========== Holdings View ==========
+
+/// Aggregates a user's open positions per asset across every plan type.
+///
+/// Only open positions count: withdrawn locks and goals are skipped, and group
+/// holdings are the user's recorded contributions to groups they still belong to.
+pub fn get_user_holdings(env: &Env, user: &Address) -> Vec<AssetHolding> {
+    let mut holdings: Vec<AssetHolding> = Vec::new(env);
+
+    for asset in flexi::get_user_flexi_assets(env, user).iter() {
+        let balance = flexi::get_flexi_balance(env, user.clone(), asset.clone()).unwrap_or(0);
+        add_holding(&mut holdings, &asset, |h| h.flexi += balance);
+    }
+
+    for lock_id in lock::get_user_lock_saves(env, user).iter() {
+        if let Some(lock_save) = lock::get_lock_save(env, lock_id) {
+            if !lock_save.is_withdrawn {
+                add_holding(&mut holdings, &lock_save.asset, |h| {
+                    h.lock += lock_save.amount
+                });
+            }
+        }
+    }
+
+    for goal_id in goal::get_user_goal_saves(env, user).iter() {
+        if let Some(goal_save) = goal::get_goal_save(env, goal_id) {
+            if !goal_save.is_withdrawn {
+                add_holding(&mut holdings, &goal_save.asset, |h| {
+                    h.goal += goal_save.current_amount
+                });
+            }
+        }
+    }
+
+    for group_id in group::get_user_groups(env, user).iter() {
+        if let Some(group_save) = group::get_group_save(env, group_id) {
+            let contribution = group::get_member_contribution(env, group_id, user);
+            add_holding(&mut holdings, &group_save.asset, |h| {
+                h.group += contribution
+            });
+        }
+    }
+
+    holdings
+}
+
+fn add_holding(
+    holdings: &mut Vec<AssetHolding>,
+    asset: &Address,
+    apply: impl FnOnce(&mut AssetHolding),
+) {
+    let index = holdings.iter().position(|h| h.asset == *asset);
+    let mut holding = match index {
+        Some(i) => holdings.get(i as u32).unwrap(),
+        None => AssetHolding {
+            asset: asset.clone(),
+            flexi: 0,
+            lock: 0,
+            goal: 0,
+            group: 0,
+            total: 0,
+        },
+    };
+
+    apply(&mut holding);
+    holding.total = holding.flexi + holding.lock + holding.goal + holding.group;
+
+    match index {
+        Some(i) => holdings.set(i as u32, holding),
+        None => holdings.push_back(holding),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NesteraContract, NesteraContractClient, SavingsError};
+    use soroban_sdk::{
+        testutils::Address as _,
+        token::{StellarAssetClient, TokenClient},
+        Address, BytesN, Env, Symbol,
+    };
+
+    fn setup() -> (Env, NesteraContractClient<'static>, Address, Address) {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+
+        let usdc = new_asset(&env);
+        client.set_settlement_asset(&admin, &usdc);
+        client.set_lock_rate(&admin, &0, &500);
+
+        (env, client, admin, usdc)
+    }
+
+    fn new_asset(env: &Env) -> Address {
+        env.register_stellar_asset_contract_v2(Address::generate(env))
+            .address()
+    }
+
+    fn new_user(env: &Env, client: &NesteraContractClient, assets: &[&Address]) -> Address {
+        let user = Address::generate(env);
+        client.initialize_user(&user);
+        for asset in assets {
+            StellarAssetClient::new(env, asset).mint(&user, &100_000);
+        }
+        user
+    }
+
+    #[test]
+    fn test_settlement_asset_is_supported_by_default() {
+        let (_env, client, _admin, usdc) = setup();
+
+        let supported = client.get_supported_assets();
+        assert_eq!(supported.len(), 1);
+        assert_eq!(supported.get(0).unwrap(), usdc);
+    }
+
+    #[test]
+    fn test_only_admin_can_manage_allowlist() {
+        let (env, client, _admin, _usdc) = setup();
+        let stranger = Address::generate(&env);
+        let eurc = new_asset(&env);
+
+        let result = client.try_add_supported_asset(&stranger, &eurc);
+        assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_flexi_balances_are_kept_per_asset() {
+        let (env, client, admin, usdc) = setup();
+        let eurc = new_asset(&env);
+        client.add_supported_asset(&admin, &eurc);
+        let user = new_user(&env, &client, &[&usdc, &eurc]);
+
+        client.deposit_flexi_asset(&user, &usdc, &4_000);
+        client.deposit_flexi_asset(&user, &eurc, &1_500);
+        client.withdraw_flexi_asset(&user, &eurc, &500);
+
+        assert_eq!(client.get_flexi_asset_balance(&user, &usdc), 4_000);
+        assert_eq!(client.get_flexi_asset_balance(&user, &eurc), 1_000);
+        assert_eq!(client.get_flexi_balance(&user), 4_000);
+        assert_eq!(TokenClient::new(&env, &eurc).balance(&user), 99_000);
+
+        // Withdrawing more than the per-asset balance fails even though the
+        // user holds more in another asset
+        let result = client.try_withdraw_flexi_asset(&user, &eurc, &2_000);
+        assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+    }
+
+    #[test]
+    fn test_unsupported_asset_is_rejected() {
+        let (env, client, _admin, usdc) = setup();
+        let unknown = new_asset(&env);
+        let user = new_user(&env, &client, &[&usdc, &unknown]);
+
+        let result = client.try_deposit_flexi_asset(&user, &unknown, &1_000);
+        assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+
+        let result = client.try_create_lock_save_asset(&user, &unknown, &1_000, &86_400);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_removed_asset_blocks_deposits_but_not_withdrawals() {
+        let (env, client, admin, usdc) = setup();
+        let eurc = new_asset(&env);
+        client.add_supported_asset(&admin, &eurc);
+        let user = new_user(&env, &client, &[&usdc, &eurc]);
+
+        client.deposit_flexi_asset(&user, &eurc, &1_000);
+        client.remove_supported_asset(&admin, &eurc);
+
+        let result = client.try_deposit_flexi_asset(&user, &eurc, &1_000);
+        assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+
+        client.withdraw_flexi_asset(&user, &eurc, &1_000);
+        assert_eq!(TokenClient::new(&env, &eurc).balance(&user), 100_000);
+
+        let result = client.try_remove_supported_asset(&admin, &eurc);
+        assert_eq!(result, Err(Ok(SavingsError::UnsupportedAsset)));
+    }
+
+    #[test]
+    fn test_plans_record_their_asset() {
+        let (env, client, admin, usdc) = setup();
+        let eurc = new_asset(&env);
+        client.add_supported_asset(&admin, &eurc);
+        let user = new_user(&env, &client, &[&usdc, &eurc]);
+
+        let lock_id = client.create_lock_save_asset(&user, &eurc, &2_000, &86_400);
+        let goal_id = client.create_goal_save_asset(
+            &user,
+            &eurc,
+            &Symbol::new(&env, "trip"),
+            &5_000,
+            &500,
+            &None,
+        );
+
+        assert_eq!(client.get_lock_save_detail(&lock_id).asset, eurc);
+        assert_eq!(client.get_goal_save_detail(&goal_id).asset, eurc);
+        assert_eq!(TokenClient::new(&env, &eurc).balance(&user), 97_500);
+        assert_eq!(TokenClient::new(&env, &usdc).balance(&user), 100_000);
+    }
+
+    #[test]
+    fn test_group_asset_can_only_change_before_contributions() {
+        let (env, client, admin, usdc) = setup();
+        let eurc = new_asset(&env);
+        client.add_supported_asset(&admin, &eurc);
+        let creator = new_user(&env, &client, &[&usdc, &eurc]);
+
+        let group_id = client.create_group_save(
+            &creator,
+            &soroban_sdk::String::from_str(&env, "Trip"),
+            &soroban_sdk::String::from_str(&env, "Summer trip"),
+            &soroban_sdk::String::from_str(&env, "travel"),
+            &10_000,
+            &1,
+            &100,
+            &true,
+            &0,
+            &1_000,
+        );
+
+        client.set_group_asset(&creator, &group_id, &eurc);
+        client.contribute_to_group_save(&creator, &group_id, &700);
+        assert_eq!(TokenClient::new(&env, &eurc).balance(&creator), 99_300);
+
+        let result = client.try_set_group_asset(&creator, &group_id, &usdc);
+        assert_eq!(result, Err(Ok(SavingsError::InvalidGroupConfig)));
+    }
+
+    #[test]
+    fn test_holdings_are_reported_per_asset() {
+        let (env, client, admin, usdc) = setup();
+        let eurc = new_asset(&env);
+        client.add_supported_asset(&admin, &eurc);
+        let user = new_user(&env, &client, &[&usdc, &eurc]);
+
+        client.deposit_flexi_asset(&user, &usdc, &1_000);
+        client.create_lock_save_asset(&user, &usdc, &2_000, &86_400);
+        client.create_goal_save_asset(
+            &user,
+            &eurc,
+            &Symbol::new(&env, "car"),
+            &9_000,
+            &3_000,
+            &None,
+        );
+        client.deposit_flexi_asset(&user, &eurc, &400);
+
+        let holdings = client.get_user_holdings(&user);
+        assert_eq!(holdings.len(), 2);
+
+        let usdc_holding = holdings.get(0).unwrap();
+        assert_eq!(usdc_holding.asset, usdc);
+        assert_eq!(usdc_holding.flexi, 1_000);
+        assert_eq!(usdc_holding.lock, 2_000);
+        assert_eq!(usdc_holding.goal, 0);
+        assert_eq!(usdc_holding.total, 3_000);
+
+        let eurc_holding = holdings.get(1).unwrap();
+        assert_eq!(eurc_holding.asset, eurc);
+        assert_eq!(eurc_holding.flexi, 400);
+        assert_eq!(eurc_holding.goal, 3_000);
+        assert_eq!(eurc_holding.total, 3_400);
+    }
+}
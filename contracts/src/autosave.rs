@@ -1,11 +1,24 @@
+use crate::assets::Funding;
+use crate::config;
 use crate::errors::SavingsError;
 use crate::flexi;
-use crate::storage_types::{AutoSave, DataKey};
+use crate::goal;
+use crate::group;
+use crate::keeper::TipBatch;
+use crate::lock;
+use crate::rates;
+use crate::storage_types::{
+    AutoSave, AutoSaveDataKey, AutoSaveDestination, AutoSaveStatus, DataKey, MissedIntervalPolicy,
+};
 use crate::ttl;
 use crate::users;
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{panic_with_error, symbol_short, token, Address, Env, Symbol, Vec};
 
-/// Creates a new AutoSave schedule for recurring Flexi deposits
+/// Creates a new AutoSave schedule for recurring deposits
+///
+/// Executions pull each amount with `transfer_from` against a token
+/// allowance the user grants this contract (`approve`), so a keeper can run
+/// the schedule without a fresh signature.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -13,34 +26,44 @@ use soroban_sdk::{Address, Env, Vec};
 /// * `amount` - The amount to deposit on each execution (must be > 0)
 /// * `interval_seconds` - How often the schedule runs in seconds (must be > 0)
 /// * `start_time` - Unix timestamp for the first execution
+/// * `destination` - The plan each execution deposits into
+/// * `allowance_expiry_ledger` - Expiration ledger of the user's allowance
+/// * `missed_policy` - How a late execution handles missed intervals
 ///
 /// # Returns
 /// * `Ok(u64)` - The unique schedule ID
 /// * `Err(SavingsError)` - If validation fails
+// Takes the entrypoint's parameters as they arrive from the caller
+#[allow(clippy::too_many_arguments)]
 pub fn create_autosave(
     env: &Env,
     user: Address,
     amount: i128,
     interval_seconds: u64,
     start_time: u64,
+    destination: AutoSaveDestination,
+    allowance_expiry_ledger: u32,
+    missed_policy: MissedIntervalPolicy,
 ) -> Result<u64, SavingsError> {
     user.require_auth();
 
-    // Validate amount
-    if amount <= 0 {
-        return Err(SavingsError::InvalidAmount);
+    validate_terms(amount, interval_seconds)?;
+
+    // Ensure user exists
+    if !users::user_exists(env, &user) {
+        return Err(SavingsError::UserNotFound);
     }
 
-    // Validate interval
-    if interval_seconds == 0 {
+    if allowance_expiry_ledger < env.ledger().sequence() {
         return Err(SavingsError::InvalidTimestamp);
     }
 
-    // Ensure user exists
-    if !users::user_exists(env, &user) {
-        return Err(SavingsError::UserNotFound);
+    if missed_policy == MissedIntervalPolicy::CatchUp(0) {
+        return Err(SavingsError::InvalidPlanConfig);
     }
 
+    validate_destination(env, &user, &destination)?;
+
     // Generate unique schedule ID
     let schedule_id = get_next_schedule_id(env);
 
@@ -51,13 +74,17 @@ pub fn create_autosave(
         amount,
         interval_seconds,
         next_execution_time: start_time,
-        is_active: true,
+        status: AutoSaveStatus::Active,
+        destination,
+        allowance_expiry_ledger,
+        consecutive_failures: 0,
+        last_failure_reason: None,
+        last_success_time: None,
+        missed_policy,
     };
 
-    // Store the schedule
-    env.storage()
-        .persistent()
-        .set(&DataKey::AutoSave(schedule_id), &schedule);
+    // Store the schedule and add it to the due index
+    save_schedule(env, &schedule);
 
     // Link schedule to user
     add_schedule_to_user(env, &user, schedule_id);
@@ -65,8 +92,7 @@ pub fn create_autosave(
     // Increment the next schedule ID
     increment_next_schedule_id(env);
 
-    // Extend TTL for new schedule and user list
-    ttl::extend_autosave_ttl(env, schedule_id);
+    // Extend TTL for the user list
     ttl::extend_user_plan_list_ttl(env, &DataKey::UserAutoSaves(user.clone()));
 
     Ok(schedule_id)
@@ -79,9 +105,11 @@ pub fn create_autosave(
 /// * `schedule_id` - The ID of the schedule to execute
 ///
 /// # Returns
-/// * `Ok(())` - If execution succeeds
-/// * `Err(SavingsError)` - If the schedule is not found, inactive, or not yet due
-pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError> {
+/// * `Ok(u32)` - The number of intervals deposited, per the schedule's
+///   missed-interval policy
+/// * `Err(SavingsError)` - If the schedule is not found, inactive, not yet due, or its
+///   destination can no longer take deposits
+pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<u32, SavingsError> {
     // Fetch the schedule
     let mut schedule: AutoSave = env
         .storage()
@@ -90,7 +118,7 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
         .ok_or(SavingsError::PlanNotFound)?;
 
     // Ensure schedule is active
-    if !schedule.is_active {
+    if schedule.status != AutoSaveStatus::Active {
         return Err(SavingsError::InvalidPlanConfig);
     }
 
@@ -100,21 +128,14 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
         return Err(SavingsError::InvalidTimestamp);
     }
 
-    // Perform Flexi deposit
-    flexi::flexi_deposit(env.clone(), schedule.user.clone(), schedule.amount)?;
-
-    // Update next execution time
-    schedule.next_execution_time += schedule.interval_seconds;
-
-    // Save updated schedule
-    env.storage()
-        .persistent()
-        .set(&DataKey::AutoSave(schedule_id), &schedule);
+    let Some((periods, _, _)) = run_schedule(env, &mut schedule)? else {
+        return Err(SavingsError::PlanCompleted);
+    };
 
-    // Extend TTL on execution (active schedule gets full extension)
-    ttl::extend_autosave_ttl(env, schedule_id);
+    // Save updated schedule, moving it to its next due bucket
+    save_schedule(env, &schedule);
 
-    Ok(())
+    Ok(periods)
 }
 
 /// Batch-executes multiple AutoSave schedules that are due.
@@ -125,6 +146,7 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
 ///
 /// # Arguments
 /// * `env` - The contract environment
+/// * `keeper` - The caller to tip, if any; must authorize the call
 /// * `schedule_ids` - A vector of schedule IDs to attempt execution on
 ///
 /// # Returns
@@ -136,11 +158,26 @@ pub fn execute_autosave(env: &Env, schedule_id: u64) -> Result<(), SavingsError>
 /// # Guarantees
 /// - One failed or skipped schedule does **not** revert the entire batch.
 /// - Only schedules whose `next_execution_time <= current_ledger_timestamp` are executed.
-/// - For each executed schedule, a Flexi deposit is performed and `next_execution_time` is
-///   advanced by `interval_seconds`.
-pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
+/// - For each executed schedule, a deposit into its destination is performed and
+///   `next_execution_time` is advanced according to its missed-interval policy.
+///   The number of intervals processed is reported in an `as_exec` event.
+/// - A schedule whose goal or group has been completed or left is deactivated.
+/// - A failed execution is recorded on the schedule and emits an `as_fail`
+///   event; after `AutoSaveMaxFailures` failures in a row it is deactivated.
+/// - A registered keeper is tipped per the `KeeperTipConfig` for each executed
+///   schedule, paid from accrued protocol fees at the end of the batch.
+pub fn execute_due_autosaves(
+    env: &Env,
+    keeper: Option<Address>,
+    schedule_ids: Vec<u64>,
+) -> Vec<bool> {
+    if let Some(keeper) = &keeper {
+        keeper.require_auth();
+    }
+
     let current_time = env.ledger().timestamp();
     let mut results = Vec::new(env);
+    let mut tips = TipBatch::new(env, keeper);
 
     for i in 0..schedule_ids.len() {
         let schedule_id = schedule_ids.get(i).unwrap();
@@ -151,7 +188,7 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
             .persistent()
             .get(&DataKey::AutoSave(schedule_id));
 
-        let schedule = match maybe_schedule {
+        let mut schedule = match maybe_schedule {
             Some(s) => s,
             None => {
                 results.push_back(false);
@@ -159,8 +196,8 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
             }
         };
 
-        // Skip inactive schedules
-        if !schedule.is_active {
+        // Skip paused, cancelled and deactivated schedules
+        if schedule.status != AutoSaveStatus::Active {
             results.push_back(false);
             continue;
         }
@@ -171,25 +208,27 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
             continue;
         }
 
-        // Attempt the Flexi deposit; if it fails, mark as false and continue
-        let deposit_result =
-            flexi::flexi_deposit(env.clone(), schedule.user.clone(), schedule.amount);
-
-        if deposit_result.is_err() {
-            results.push_back(false);
-            continue;
-        }
+        // Attempt the deposit; a failure is recorded and the batch continues,
+        // but a schedule deactivated for a closed destination has not failed
+        let executed = match run_schedule(env, &mut schedule) {
+            Ok(Some((_, asset, amount))) => {
+                tips.add(&asset, amount)
+                    .unwrap_or_else(|e| panic_with_error!(env, e));
+                true
+            }
+            Ok(None) => false,
+            Err(err) => {
+                record_failure(env, &mut schedule, err);
+                false
+            }
+        };
 
-        // Update next execution time and persist
-        let mut updated_schedule = schedule.clone();
-        updated_schedule.next_execution_time += updated_schedule.interval_seconds;
-        env.storage()
-            .persistent()
-            .set(&DataKey::AutoSave(schedule_id), &updated_schedule);
+        save_schedule(env, &schedule);
 
-        results.push_back(true);
+        results.push_back(executed);
     }
 
+    tips.pay(env).unwrap_or_else(|e| panic_with_error!(env, e));
     results
 }
 
@@ -206,25 +245,137 @@ pub fn execute_due_autosaves(env: &Env, schedule_ids: Vec<u64>) -> Vec<bool> {
 pub fn cancel_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
     user.require_auth();
 
-    // Fetch the schedule
-    let mut schedule: AutoSave = env
-        .storage()
-        .persistent()
-        .get(&DataKey::AutoSave(schedule_id))
-        .ok_or(SavingsError::PlanNotFound)?;
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
 
-    // Ensure caller owns the schedule
-    if schedule.user != user {
-        return Err(SavingsError::Unauthorized);
+    // Deactivate the schedule
+    schedule.status = AutoSaveStatus::Cancelled;
+
+    // Save updated schedule, dropping it from the due index
+    save_schedule(env, &schedule);
+
+    Ok(())
+}
+
+/// Pauses an active AutoSave schedule until its owner resumes it.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the schedule does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the schedule
+/// * `SavingsError::PlanCompleted` - If the schedule was cancelled
+/// * `SavingsError::InvalidPlanConfig` - If the schedule is not active
+pub fn pause_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    match schedule.status {
+        AutoSaveStatus::Active => {}
+        AutoSaveStatus::Cancelled => return Err(SavingsError::PlanCompleted),
+        _ => return Err(SavingsError::InvalidPlanConfig),
     }
 
-    // Deactivate the schedule
-    schedule.is_active = false;
+    schedule.status = AutoSaveStatus::Paused;
+    save_schedule(env, &schedule);
 
-    // Save updated schedule
-    env.storage()
-        .persistent()
-        .set(&DataKey::AutoSave(schedule_id), &schedule);
+    env.events()
+        .publish((symbol_short!("as_pause"), user, schedule_id), ());
+    Ok(())
+}
+
+/// Resumes a paused or deactivated AutoSave schedule, with its next run one
+/// interval from now.
+///
+/// The destination is validated again, and the failure count is reset.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the schedule does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the schedule
+/// * `SavingsError::PlanCompleted` - If the schedule was cancelled or its
+///   destination can no longer take deposits
+/// * `SavingsError::InvalidPlanConfig` - If the schedule is already active
+pub fn resume_autosave(env: &Env, user: Address, schedule_id: u64) -> Result<u64, SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    match schedule.status {
+        AutoSaveStatus::Paused | AutoSaveStatus::Deactivated => {}
+        AutoSaveStatus::Cancelled => return Err(SavingsError::PlanCompleted),
+        AutoSaveStatus::Active => return Err(SavingsError::InvalidPlanConfig),
+    }
+    validate_destination(env, &user, &schedule.destination)?;
+
+    schedule.next_execution_time = env
+        .ledger()
+        .timestamp()
+        .checked_add(schedule.interval_seconds)
+        .ok_or(SavingsError::Overflow)?;
+    schedule.status = AutoSaveStatus::Active;
+    schedule.consecutive_failures = 0;
+    save_schedule(env, &schedule);
+
+    env.events().publish(
+        (symbol_short!("as_resume"), user, schedule_id),
+        schedule.next_execution_time,
+    );
+    Ok(schedule.next_execution_time)
+}
+
+/// Changes the amount and interval of an AutoSave schedule, validated as in
+/// `create_autosave`. The next execution time is left unchanged.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the schedule does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the schedule
+/// * `SavingsError::PlanCompleted` - If the schedule was cancelled
+/// * `SavingsError::InvalidAmount` - If amount <= 0
+/// * `SavingsError::InvalidTimestamp` - If interval_seconds is 0
+pub fn update_autosave(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    amount: i128,
+    interval_seconds: u64,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if schedule.status == AutoSaveStatus::Cancelled {
+        return Err(SavingsError::PlanCompleted);
+    }
+    validate_terms(amount, interval_seconds)?;
+
+    schedule.amount = amount;
+    schedule.interval_seconds = interval_seconds;
+    save_schedule(env, &schedule);
+
+    env.events().publish(
+        (symbol_short!("as_update"), user, schedule_id),
+        (amount, interval_seconds),
+    );
+    Ok(())
+}
+
+/// Records a renewed token allowance for a schedule.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the schedule does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the schedule
+/// * `SavingsError::InvalidTimestamp` - If the expiry ledger has already passed
+pub fn set_autosave_allowance_expiry(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    allowance_expiry_ledger: u32,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+
+    if allowance_expiry_ledger < env.ledger().sequence() {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    schedule.allowance_expiry_ledger = allowance_expiry_ledger;
+    save_schedule(env, &schedule);
 
     Ok(())
 }
@@ -244,25 +395,411 @@ pub fn get_autosave(env: &Env, schedule_id: u64) -> Option<AutoSave> {
     schedule
 }
 
-/// Gets all AutoSave schedule IDs for a user
-pub fn get_user_autosaves(env: &Env, user: &Address) -> Vec<u64> {
+/// Gets all of a user's AutoSave schedules, with their current status
+pub fn get_user_autosaves(env: &Env, user: &Address) -> Vec<AutoSave> {
     let list_key = DataKey::UserAutoSaves(user.clone());
-    let schedules = env
+    let schedule_ids: Vec<u64> = env
         .storage()
         .persistent()
         .get(&list_key)
         .unwrap_or(Vec::new(env));
 
     // Extend TTL on list access
-    if schedules.len() > 0 {
+    if schedule_ids.len() > 0 {
         ttl::extend_user_plan_list_ttl(env, &list_key);
     }
 
+    let mut schedules = Vec::new(env);
+    for schedule_id in schedule_ids.iter() {
+        if let Some(schedule) = get_autosave(env, schedule_id) {
+            schedules.push_back(schedule);
+        }
+    }
     schedules
 }
 
+// ========== Due Index ==========
+
+/// Width of a due-index bucket: active schedules are grouped by the hour of
+/// their `next_execution_time`
+const DUE_BUCKET_SECONDS: u64 = 3600;
+
+/// Returns up to `limit` IDs of active schedules due at `now`, earliest
+/// `next_execution_time` first, for keepers to pass to
+/// `execute_due_autosaves`.
+pub fn get_due_autosaves(env: &Env, now: u64, limit: u32) -> Vec<u64> {
+    let mut due_ids = Vec::new(env);
+    let last_bucket = due_bucket(now);
+
+    for bucket in get_due_buckets(env).iter() {
+        if bucket > last_bucket || due_ids.len() >= limit {
+            break;
+        }
+
+        // Order the bucket's schedules by execution time, then ID
+        let mut entries: Vec<(u64, u64)> = Vec::new(env);
+        for schedule_id in get_bucket(env, bucket).iter() {
+            let Some(schedule) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, AutoSave>(&DataKey::AutoSave(schedule_id))
+            else {
+                continue;
+            };
+            let entry = (schedule.next_execution_time, schedule_id);
+            if entry.0 > now {
+                continue;
+            }
+            let at = entries.binary_search(entry).unwrap_or_else(|i| i);
+            entries.insert(at, entry);
+        }
+
+        for (_, schedule_id) in entries.iter() {
+            if due_ids.len() >= limit {
+                break;
+            }
+            due_ids.push_back(schedule_id);
+        }
+    }
+
+    due_ids
+}
+
+fn due_bucket(timestamp: u64) -> u64 {
+    timestamp / DUE_BUCKET_SECONDS
+}
+
+fn get_due_buckets(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveDataKey::DueBuckets)
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_bucket(env: &Env, bucket: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveDataKey::DueBucket(bucket))
+        .unwrap_or(Vec::new(env))
+}
+
+fn add_to_bucket(env: &Env, bucket: u64, schedule_id: u64) {
+    let mut ids = get_bucket(env, bucket);
+    if ids.is_empty() {
+        let mut buckets = get_due_buckets(env);
+        if let Err(at) = buckets.binary_search(bucket) {
+            buckets.insert(at, bucket);
+        }
+        set_index_entry(env, &AutoSaveDataKey::DueBuckets, &buckets);
+    }
+    ids.push_back(schedule_id);
+    set_index_entry(env, &AutoSaveDataKey::DueBucket(bucket), &ids);
+}
+
+fn remove_from_bucket(env: &Env, bucket: u64, schedule_id: u64) {
+    let mut ids = get_bucket(env, bucket);
+    if let Some(at) = ids.first_index_of(schedule_id) {
+        ids.remove(at);
+    }
+
+    if !ids.is_empty() {
+        set_index_entry(env, &AutoSaveDataKey::DueBucket(bucket), &ids);
+        return;
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&AutoSaveDataKey::DueBucket(bucket));
+    let mut buckets = get_due_buckets(env);
+    if let Ok(at) = buckets.binary_search(bucket) {
+        buckets.remove(at);
+    }
+    set_index_entry(env, &AutoSaveDataKey::DueBuckets, &buckets);
+}
+
+fn set_index_entry(env: &Env, key: &AutoSaveDataKey, ids: &Vec<u64>) {
+    env.storage().persistent().set(key, ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
+
 // ========== Helper Functions ==========
 
+/// Checks that `destination` can receive deposits from `user`.
+fn validate_destination(
+    env: &Env,
+    user: &Address,
+    destination: &AutoSaveDestination,
+) -> Result<(), SavingsError> {
+    match destination {
+        AutoSaveDestination::Flexi => Ok(()),
+        AutoSaveDestination::Goal(goal_id) => {
+            let goal_save = goal::get_goal_save(env, *goal_id).ok_or(SavingsError::PlanNotFound)?;
+            if goal_save.owner != *user {
+                return Err(SavingsError::Unauthorized);
+            }
+            if goal_save.is_completed || goal_save.is_withdrawn {
+                return Err(SavingsError::PlanCompleted);
+            }
+            Ok(())
+        }
+        AutoSaveDestination::Group(group_id) => {
+            let group_save =
+                group::get_group_save(env, *group_id).ok_or(SavingsError::PlanNotFound)?;
+            if !group::get_group_members(env, *group_id).contains(user) {
+                return Err(SavingsError::NotGroupMember);
+            }
+            if group_save.is_completed {
+                return Err(SavingsError::PlanCompleted);
+            }
+            Ok(())
+        }
+        AutoSaveDestination::Lock(duration) => {
+            if *duration == 0 {
+                return Err(SavingsError::InvalidTimestamp);
+            }
+            // Every run must find a LockRate tier for the duration
+            rates::get_lock_rate_for_duration(env, *duration)?;
+            Ok(())
+        }
+    }
+}
+
+/// Whether the schedule's goal or group can still take deposits.
+fn destination_is_open(env: &Env, schedule: &AutoSave) -> bool {
+    validate_destination(env, &schedule.user, &schedule.destination).is_ok()
+}
+
+/// Deposits the due intervals into the schedule's destination in one
+/// transfer and advances `next_execution_time`, returning how many
+/// intervals were deposited, and the asset and total amount.
+///
+/// A schedule whose destination has been completed, withdrawn or left is
+/// deactivated instead, returning `None`, and one whose deposit completes its
+/// goal or group is deactivated after it.
+fn run_schedule(
+    env: &Env,
+    schedule: &mut AutoSave,
+) -> Result<Option<(u32, Address, i128)>, SavingsError> {
+    if !destination_is_open(env, schedule) {
+        deactivate(env, schedule);
+        return Ok(None);
+    }
+
+    let (periods, next_execution_time) = plan_periods(env, schedule)?;
+    let amount = schedule
+        .amount
+        .checked_mul(periods as i128)
+        .ok_or(SavingsError::Overflow)?;
+
+    let asset = destination_asset(env, &schedule.destination)?;
+    check_allowance(env, schedule, &asset, amount)?;
+
+    let user = schedule.user.clone();
+    let funding = Funding::Allowance;
+    match schedule.destination {
+        AutoSaveDestination::Flexi => {
+            flexi::flexi_deposit_funded(env.clone(), user, asset.clone(), amount, funding)?;
+        }
+        AutoSaveDestination::Goal(goal_id) => {
+            goal::deposit_to_goal_save_funded(env, user, goal_id, amount, funding)?;
+        }
+        AutoSaveDestination::Group(group_id) => {
+            group::contribute_to_group_save_funded(env, user, group_id, amount, funding)?;
+        }
+        AutoSaveDestination::Lock(duration) => {
+            lock::create_lock_save_funded(env, user, asset.clone(), amount, duration, funding)?;
+        }
+    }
+
+    schedule.next_execution_time = next_execution_time;
+    schedule.consecutive_failures = 0;
+    schedule.last_success_time = Some(env.ledger().timestamp());
+
+    env.events().publish(
+        (symbol_short!("as_exec"), schedule.user.clone(), schedule.id),
+        (periods, amount),
+    );
+
+    if !destination_is_open(env, schedule) {
+        deactivate(env, schedule);
+    }
+
+    Ok(Some((periods, asset, amount)))
+}
+
+/// Intervals to deposit now under the schedule's missed-interval policy,
+/// and the next execution time once they are.
+fn plan_periods(env: &Env, schedule: &AutoSave) -> Result<(u32, u64), SavingsError> {
+    let interval = schedule.interval_seconds;
+    let overdue = env
+        .ledger()
+        .timestamp()
+        .saturating_sub(schedule.next_execution_time);
+    // Slots at or before now, including the one that made the schedule due
+    let due = (overdue / interval).saturating_add(1);
+
+    let periods = match schedule.missed_policy {
+        MissedIntervalPolicy::Single | MissedIntervalPolicy::SkipMissed => 1,
+        MissedIntervalPolicy::CatchUp(max_periods) => due.min(max_periods as u64),
+    };
+    // Skipping moves past every due slot; otherwise only the deposited ones
+    let advanced = match schedule.missed_policy {
+        MissedIntervalPolicy::SkipMissed => due,
+        _ => periods,
+    };
+
+    let next_execution_time = advanced
+        .checked_mul(interval)
+        .and_then(|delta| schedule.next_execution_time.checked_add(delta))
+        .ok_or(SavingsError::Overflow)?;
+
+    Ok((periods as u32, next_execution_time))
+}
+
+/// Default for `AutoSaveMaxFailures` when the admin has not set one
+const DEFAULT_MAX_FAILURES: u32 = 3;
+
+/// Consecutive failed executions after which a schedule is deactivated.
+pub fn get_max_failures(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&AutoSaveDataKey::MaxFailures)
+        .unwrap_or(DEFAULT_MAX_FAILURES)
+}
+
+/// Records a failed execution, deactivating the schedule once it has failed
+/// `AutoSaveMaxFailures` times in a row.
+fn record_failure(env: &Env, schedule: &mut AutoSave, err: SavingsError) {
+    let code = err as u32;
+    schedule.consecutive_failures = schedule.consecutive_failures.saturating_add(1);
+    schedule.last_failure_reason = Some(code);
+
+    env.events().publish(
+        (symbol_short!("as_fail"), schedule.user.clone(), schedule.id),
+        (code, schedule.consecutive_failures),
+    );
+
+    if schedule.status == AutoSaveStatus::Active
+        && schedule.consecutive_failures >= get_max_failures(env)
+    {
+        deactivate(env, schedule);
+    }
+}
+
+/// The asset a destination takes deposits in.
+fn destination_asset(
+    env: &Env,
+    destination: &AutoSaveDestination,
+) -> Result<Address, SavingsError> {
+    match destination {
+        AutoSaveDestination::Goal(goal_id) => goal::get_goal_save(env, *goal_id)
+            .map(|goal_save| goal_save.asset)
+            .ok_or(SavingsError::PlanNotFound),
+        AutoSaveDestination::Group(group_id) => group::get_group_save(env, *group_id)
+            .map(|group_save| group_save.asset)
+            .ok_or(SavingsError::PlanNotFound),
+        AutoSaveDestination::Flexi | AutoSaveDestination::Lock(_) => {
+            config::get_settlement_asset(env)
+        }
+    }
+}
+
+/// Fails with an `autosave_allowance` event if the user's allowance has
+/// expired or no longer covers one execution.
+///
+/// The wallet balance is checked too, so the `transfer_from` made after the
+/// deposit is booked cannot fail and abort the whole batch.
+fn check_allowance(
+    env: &Env,
+    schedule: &AutoSave,
+    asset: &Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    let token = token::Client::new(env, asset);
+    let allowance = token.allowance(&schedule.user, &env.current_contract_address());
+    let expired = env.ledger().sequence() > schedule.allowance_expiry_ledger;
+
+    if expired || allowance < amount {
+        env.events().publish(
+            (
+                Symbol::new(env, "autosave_allowance"),
+                schedule.user.clone(),
+                schedule.id,
+            ),
+            (allowance, amount, schedule.allowance_expiry_ledger),
+        );
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    if token.balance(&schedule.user) < amount {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    Ok(())
+}
+
+/// Amount and interval rules shared by `create_autosave` and `update_autosave`.
+fn validate_terms(amount: i128, interval_seconds: u64) -> Result<(), SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if interval_seconds == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+    Ok(())
+}
+
+/// Loads a schedule, checking that `user` owns it.
+fn get_owned_schedule(
+    env: &Env,
+    user: &Address,
+    schedule_id: u64,
+) -> Result<AutoSave, SavingsError> {
+    let schedule: AutoSave = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AutoSave(schedule_id))
+        .ok_or(SavingsError::PlanNotFound)?;
+
+    if schedule.user != *user {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(schedule)
+}
+
+/// Stores a schedule, moving it within the due index if its status or
+/// next execution time changed.
+fn save_schedule(env: &Env, schedule: &AutoSave) {
+    let key = DataKey::AutoSave(schedule.id);
+    let previous: Option<AutoSave> = env.storage().persistent().get(&key);
+    let indexed_at = |s: &AutoSave| {
+        (s.status == AutoSaveStatus::Active).then(|| due_bucket(s.next_execution_time))
+    };
+    let from = previous.as_ref().and_then(indexed_at);
+    let to = indexed_at(schedule);
+    if from != to {
+        if let Some(bucket) = from {
+            remove_from_bucket(env, bucket, schedule.id);
+        }
+        if let Some(bucket) = to {
+            add_to_bucket(env, bucket, schedule.id);
+        }
+    }
+
+    env.storage().persistent().set(&key, schedule);
+    ttl::extend_autosave_ttl(env, schedule.id);
+}
+
+fn deactivate(env: &Env, schedule: &mut AutoSave) {
+    schedule.status = AutoSaveStatus::Deactivated;
+    env.events().publish(
+        (symbol_short!("as_off"), schedule.user.clone(), schedule.id),
+        schedule.destination.clone(),
+    );
+}
+
 fn get_next_schedule_id(env: &Env) -> u64 {
     let counter_key = DataKey::NextAutoSaveId;
     let id = env.storage().persistent().get(&counter_key).unwrap_or(1);
@@ -1,3 +1,4 @@
+use crate::assets;
 use crate::errors::SavingsError;
 use crate::storage_types::DataKey;
 use soroban_sdk::{contracttype, symbol_short, Address, Env};
@@ -16,6 +17,8 @@ pub struct Config {
     pub treasury: Address,
     pub protocol_fee_bps: u32,
     pub paused: bool,
+    /// Stellar Asset Contract that savings are denominated and held in
+    pub settlement_asset: Option<Address>,
 }
 
 // ========== Admin Verification ==========
@@ -24,7 +27,7 @@ pub struct Config {
 ///
 /// # Errors
 /// * `SavingsError::Unauthorized` - If the caller is not the admin
-fn require_admin(env: &Env, caller: &Address) -> Result<(), SavingsError> {
+pub(crate) fn require_admin(env: &Env, caller: &Address) -> Result<(), SavingsError> {
     let stored_admin: Address = env
         .storage()
         .instance()
@@ -129,11 +132,14 @@ pub fn get_config(env: &Env) -> Result<Config, SavingsError> {
         .get(&DataKey::Paused)
         .unwrap_or(false);
 
+    let settlement_asset: Option<Address> = env.storage().instance().get(&DataKey::SettlementAsset);
+
     Ok(Config {
         admin,
         treasury,
         protocol_fee_bps,
         paused,
+        settlement_asset,
     })
 }
 
@@ -186,6 +192,48 @@ pub fn set_protocol_fee(env: &Env, admin: Address, new_fee_bps: u32) -> Result<(
     Ok(())
 }
 
+/// Sets the settlement asset used by entry points that do not name an asset.
+///
+/// The asset can only be configured once: changing it after users have
+/// deposited would leave their recorded balances backed by a different token.
+/// It is added to the supported asset allowlist automatically.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `admin` - The admin calling this function
+/// * `asset` - The Stellar Asset Contract address
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+/// * `SavingsError::ConfigAlreadyInitialized` - If an asset is already configured
+pub fn set_settlement_asset(env: &Env, admin: Address, asset: Address) -> Result<(), SavingsError> {
+    require_admin(env, &admin)?;
+
+    if env.storage().instance().has(&DataKey::SettlementAsset) {
+        return Err(SavingsError::ConfigAlreadyInitialized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::SettlementAsset, &asset);
+    assets::allow_asset(env, &asset);
+
+    env.events().publish((symbol_short!("set_asset"),), asset);
+
+    Ok(())
+}
+
+/// Returns the configured settlement asset.
+///
+/// # Errors
+/// * `SavingsError::UnsupportedAsset` - If no settlement asset has been configured
+pub fn get_settlement_asset(env: &Env) -> Result<Address, SavingsError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::SettlementAsset)
+        .ok_or(SavingsError::UnsupportedAsset)
+}
+
 /// Pauses the contract, blocking all state-changing operations.
 ///
 /// # Arguments
@@ -1,6 +1,13 @@
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, InvokeError};
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, Address, BytesN, Env, InvokeError,
+};
 
-use crate::{NesteraContract, NesteraContractClient, SavingsError};
+use crate::{
+    AutoSaveDestination, MissedIntervalPolicy, NesteraContract, NesteraContractClient, SavingsError,
+};
+
+/// Expiration ledger recorded for AutoSave allowances in these tests
+const ALLOWANCE_EXPIRY: u32 = 10_000;
 
 // ========== Test Helpers ==========
 
@@ -18,6 +25,21 @@ fn setup() -> (Env, NesteraContractClient<'static>, Address) {
     (env, client, admin)
 }
 
+/// Registers a test Stellar asset as the settlement asset and funds `user`
+fn fund_user(
+    env: &Env,
+    client: &NesteraContractClient,
+    admin: &Address,
+    user: &Address,
+    amount: i128,
+) {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.set_settlement_asset(admin, &token);
+    StellarAssetClient::new(env, &token).mint(user, &amount);
+}
+
 fn assert_savings_error(err: Result<SavingsError, InvokeError>, expected: SavingsError) {
     assert_eq!(err, Ok(expected));
 }
@@ -336,6 +358,7 @@ fn test_pause_blocks_withdraw_flexi() {
 
     env.mock_all_auths();
     client.initialize_user(&user);
+    fund_user(&env, &client, &admin, &user, 100);
     client.deposit_flexi(&user, &100);
     client.pause_contract(&admin);
 
@@ -356,7 +379,15 @@ fn test_pause_blocks_create_autosave() {
 
     assert_savings_error(
         client
-            .try_create_autosave(&user, &100, &3600, &1000)
+            .try_create_autosave(
+                &user,
+                &100,
+                &3600,
+                &1000,
+                &AutoSaveDestination::Flexi,
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            )
             .unwrap_err(),
         SavingsError::ContractPaused,
     );
@@ -371,7 +402,15 @@ fn test_pause_blocks_execute_autosave() {
     client.initialize_user(&user);
 
     // Create autosave first while not paused
-    let schedule_id = client.create_autosave(&user, &100, &3600, &0);
+    let schedule_id = client.create_autosave(
+        &user,
+        &100,
+        &3600,
+        &0,
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Now pause
     client.pause_contract(&admin);
@@ -0,0 +1,401 @@
+use crate::assets;
+use crate::config;
+use crate::errors::SavingsError;
+use crate::storage_types::{DataKey, FeeCredit, FeeDataKey, ProtocolFeeBalance};
+use crate::ttl;
+use soroban_sdk::{symbol_short, Address, Env, IntoVal, Val, Vec};
+
+/// Books a protocol fee that stays in contract custody until it is swept.
+///
+/// The fee is added to the per-asset ledger, which bounds what can later be
+/// swept, and credited in that asset to the current `FeeRecipient` if one is
+/// configured.
+///
+/// # Returns
+/// The fee recipient that was credited, so callers can emit their own event
+pub fn accrue_fee(
+    env: &Env,
+    asset: &Address,
+    amount: i128,
+) -> Result<Option<Address>, SavingsError> {
+    if amount <= 0 {
+        return Ok(None);
+    }
+
+    let asset_key = DataKey::AssetFees(asset.clone());
+    let mut asset_fees = get_ledger(env, &asset_key);
+    asset_fees.accrued = asset_fees
+        .accrued
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    set_ledger(env, &asset_key, &asset_fees);
+
+    let fee_recipient = env
+        .storage()
+        .instance()
+        .get::<DataKey, Address>(&DataKey::FeeRecipient);
+
+    // Fees are drawn down oldest first, so remember who each lot was
+    // credited to
+    let mut credits = get_credits(env, asset);
+    let last = credits.len().checked_sub(1);
+    match last.map(|i| (i, credits.get(i).unwrap())) {
+        Some((i, mut lot)) if lot.recipient == fee_recipient => {
+            lot.amount = lot
+                .amount
+                .checked_add(amount)
+                .ok_or(SavingsError::Overflow)?;
+            credits.set(i, lot);
+        }
+        _ => credits.push_back(FeeCredit {
+            recipient: fee_recipient.clone(),
+            amount,
+        }),
+    }
+    set_credits(env, asset, &credits);
+
+    if let Some(recipient) = &fee_recipient {
+        let fee_key = FeeDataKey::RecipientFees(recipient.clone(), asset.clone());
+        let mut recipient_fees = get_ledger(env, &fee_key);
+        recipient_fees.accrued = recipient_fees
+            .accrued
+            .checked_add(amount)
+            .ok_or(SavingsError::Overflow)?;
+        set_ledger(env, &fee_key, &recipient_fees);
+    }
+
+    Ok(fee_recipient)
+}
+
+/// Pays accrued fees in `asset` out of custody to `Config.treasury`.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `asset` - The asset contract the fees were collected in
+/// * `amount` - The amount to sweep (must be > 0)
+///
+/// # Errors
+/// * `SavingsError::InvalidAmount` - If amount <= 0
+/// * `SavingsError::InsufficientBalance` - If amount exceeds the unswept fees in `asset`
+pub fn sweep_fees(env: &Env, asset: Address, amount: i128) -> Result<(), SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let asset_key = DataKey::AssetFees(asset.clone());
+    let mut asset_fees = get_ledger(env, &asset_key);
+    if amount > asset_fees.accrued {
+        return Err(SavingsError::InsufficientBalance);
+    }
+    asset_fees.accrued -= amount;
+    asset_fees.swept = asset_fees
+        .swept
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    set_ledger(env, &asset_key, &asset_fees);
+    debit_recipients(env, &asset, amount, true)?;
+
+    let treasury = config::get_config(env)?.treasury;
+    assets::pay(env, &asset, &treasury, amount);
+
+    env.events()
+        .publish((symbol_short!("fee_sweep"), asset), (treasury, amount));
+
+    Ok(())
+}
+
+/// Draws up to `amount` of the unswept fees in `asset` to spend on
+/// protocol costs such as keeper tips, leaving the tokens in custody for the
+/// caller to pay out.
+///
+/// # Returns
+/// The amount drawn, which is less than `amount` if fewer fees have accrued
+pub(crate) fn spend_fees(env: &Env, asset: &Address, amount: i128) -> Result<i128, SavingsError> {
+    let asset_key = DataKey::AssetFees(asset.clone());
+    let mut asset_fees = get_ledger(env, &asset_key);
+    let spent = amount.min(asset_fees.accrued);
+    if spent <= 0 {
+        return Ok(0);
+    }
+    asset_fees.accrued -= spent;
+    set_ledger(env, &asset_key, &asset_fees);
+    debit_recipients(env, asset, spent, false)?;
+    Ok(spent)
+}
+
+/// Draws `amount` of the unswept fees in `asset` from the recipients they
+/// were credited to, oldest first. Swept fees are added to each recipient's
+/// swept total; fees spent in custody are not.
+fn debit_recipients(
+    env: &Env,
+    asset: &Address,
+    amount: i128,
+    swept: bool,
+) -> Result<(), SavingsError> {
+    let mut credits = get_credits(env, asset);
+    let mut remaining = amount;
+    while remaining > 0 {
+        let Some(mut lot) = credits.first() else {
+            break;
+        };
+        let taken = remaining.min(lot.amount);
+        remaining -= taken;
+        lot.amount -= taken;
+        if lot.amount == 0 {
+            credits.pop_front();
+        } else {
+            credits.set(0, lot.clone());
+        }
+
+        if let Some(recipient) = lot.recipient {
+            let fee_key = FeeDataKey::RecipientFees(recipient, asset.clone());
+            let mut recipient_fees = get_ledger(env, &fee_key);
+            recipient_fees.accrued = recipient_fees
+                .accrued
+                .checked_sub(taken)
+                .ok_or(SavingsError::Underflow)?;
+            if swept {
+                recipient_fees.swept = recipient_fees
+                    .swept
+                    .checked_add(taken)
+                    .ok_or(SavingsError::Overflow)?;
+            }
+            set_ledger(env, &fee_key, &recipient_fees);
+        }
+    }
+    set_credits(env, asset, &credits);
+    Ok(())
+}
+
+/// Returns the fees in `asset` credited to `recipient` that are still in
+/// custody, and the total swept to the treasury out of those credits.
+pub fn get_recipient_fee_balance(
+    env: &Env,
+    recipient: Address,
+    asset: Address,
+) -> ProtocolFeeBalance {
+    get_ledger(env, &FeeDataKey::RecipientFees(recipient, asset))
+}
+
+/// Returns the unswept and swept fee totals for a single asset.
+pub fn get_asset_fee_balance(env: &Env, asset: Address) -> ProtocolFeeBalance {
+    get_ledger(env, &DataKey::AssetFees(asset))
+}
+
+fn get_ledger<K>(env: &Env, key: &K) -> ProtocolFeeBalance
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage()
+        .persistent()
+        .get(key)
+        .unwrap_or(ProtocolFeeBalance {
+            accrued: 0,
+            swept: 0,
+        })
+}
+
+fn set_ledger<K>(env: &Env, key: &K, ledger: &ProtocolFeeBalance)
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, ledger);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
+
+fn get_credits(env: &Env, asset: &Address) -> Vec<FeeCredit> {
+    env.storage()
+        .persistent()
+        .get(&FeeDataKey::Credits(asset.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_credits(env: &Env, asset: &Address, credits: &Vec<FeeCredit>) {
+    let key = FeeDataKey::Credits(asset.clone());
+    if credits.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, credits);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{NesteraContract, NesteraContractClient, SavingsError};
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Events},
+        token::{StellarAssetClient, TokenClient},
+        Address, BytesN, Env, IntoVal,
+    };
+
+    struct Setup {
+        env: Env,
+        client: NesteraContractClient<'static>,
+        admin: Address,
+        token: Address,
+        treasury: Address,
+        fee_recipient: Address,
+    }
+
+    /// Contract with a 10% protocol fee, a fee recipient and a treasury
+    fn setup() -> Setup {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        client.set_treasury(&admin, &treasury);
+        client.set_fee_recipient(&fee_recipient);
+        client.set_protocol_fee_bps(&1_000);
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
+        Setup {
+            env,
+            client,
+            admin,
+            token,
+            treasury,
+            fee_recipient,
+        }
+    }
+
+    fn deposit(s: &Setup, amount: i128) {
+        let user = Address::generate(&s.env);
+        s.client.initialize_user(&user);
+        StellarAssetClient::new(&s.env, &s.token).mint(&user, &amount);
+        s.client.deposit_flexi(&user, &amount);
+    }
+
+    #[test]
+    fn test_sweep_pays_treasury_and_updates_ledgers() {
+        let s = setup();
+        deposit(&s, 10_000); // 1,000 fee
+
+        s.client.sweep_fees(&s.token, &600);
+
+        let token = TokenClient::new(&s.env, &s.token);
+        assert_eq!(token.balance(&s.treasury), 600);
+        assert_eq!(token.balance(&s.client.address), 9_400);
+
+        let recipient_fees = s
+            .client
+            .get_protocol_fee_balance(&s.fee_recipient, &s.token);
+        assert_eq!(recipient_fees.accrued, 400);
+        assert_eq!(recipient_fees.swept, 600);
+
+        let asset_fees = s.client.get_asset_fee_balance(&s.token);
+        assert_eq!(asset_fees.accrued, 400);
+        assert_eq!(asset_fees.swept, 600);
+    }
+
+    #[test]
+    fn test_sweep_emits_event() {
+        let s = setup();
+        deposit(&s, 10_000);
+
+        s.client.sweep_fees(&s.token, &1_000);
+
+        let events = s.env.events().all();
+        let (contract, topics, data) = events.last().unwrap();
+        assert_eq!(contract, s.client.address);
+        assert_eq!(
+            topics,
+            (symbol_short!("fee_sweep"), s.token.clone()).into_val(&s.env)
+        );
+        let (to, amount): (Address, i128) = data.into_val(&s.env);
+        assert_eq!(to, s.treasury);
+        assert_eq!(amount, 1_000);
+    }
+
+    #[test]
+    fn test_cannot_sweep_more_than_accrued() {
+        let s = setup();
+        deposit(&s, 10_000);
+
+        let result = s.client.try_sweep_fees(&s.token, &1_001);
+        assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+
+        // User principal in another asset cannot be swept either
+        let other = s
+            .env
+            .register_stellar_asset_contract_v2(Address::generate(&s.env))
+            .address();
+        s.client.add_supported_asset(&s.admin, &other);
+        let result = s.client.try_sweep_fees(&other, &1);
+        assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+
+        let result = s.client.try_sweep_fees(&s.token, &0);
+        assert_eq!(result, Err(Ok(SavingsError::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_sweep_draws_down_the_recipients_that_were_credited() {
+        let s = setup();
+        deposit(&s, 10_000); // 1,000 fee to the first recipient
+
+        let next_recipient = Address::generate(&s.env);
+        s.client.set_fee_recipient(&next_recipient);
+        deposit(&s, 10_000); // 1,000 fee to the next recipient
+
+        // Oldest fees are swept first
+        s.client.sweep_fees(&s.token, &1_500);
+
+        let first = s
+            .client
+            .get_protocol_fee_balance(&s.fee_recipient, &s.token);
+        assert_eq!(first.accrued, 0);
+        assert_eq!(first.swept, 1_000);
+        let next = s.client.get_protocol_fee_balance(&next_recipient, &s.token);
+        assert_eq!(next.accrued, 500);
+        assert_eq!(next.swept, 500);
+
+        // Credits are kept per asset
+        let other = s
+            .env
+            .register_stellar_asset_contract_v2(Address::generate(&s.env))
+            .address();
+        let other_fees = s.client.get_protocol_fee_balance(&next_recipient, &other);
+        assert_eq!(other_fees.accrued, 0);
+        assert_eq!(other_fees.swept, 0);
+    }
+
+    #[test]
+    fn test_fees_without_recipient_can_still_be_swept() {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        client.set_protocol_fee_bps(&500);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
+        client.initialize_user(&user);
+        StellarAssetClient::new(&env, &token).mint(&user, &2_000);
+        client.deposit_flexi(&user, &2_000);
+        assert_eq!(client.get_asset_fee_balance(&token).accrued, 100);
+
+        // Treasury defaults to the admin when none has been configured
+        client.sweep_fees(&token, &100);
+        assert_eq!(TokenClient::new(&env, &token).balance(&admin), 100);
+    }
+}
@@ -1,25 +1,54 @@
 // New/Correct
+use crate::assets::{self, Funding};
 use crate::calculate_fee;
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::fees;
 use crate::invariants;
+use crate::ledger::{self, Liability};
+use crate::rates;
 use crate::rewards;
-use crate::storage_types::{DataKey, User};
+use crate::storage_types::{DataKey, FlexiInterest, User};
 use crate::ttl;
-use soroban_sdk::{symbol_short, Address, Env};
-
-/// Handles depositing funds into the Flexi Save pool.
-pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
-    ensure_not_paused(&env)?;
-
+use soroban_sdk::{symbol_short, Address, Env, Vec};
+
+/// Handles depositing funds into the Flexi Save pool for a given asset.
+///
+/// The full `amount` is pulled from the user's wallet into the contract. The
+/// protocol fee share stays in custody and is credited to the `FeeRecipient`.
+pub fn flexi_deposit(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
     // 1. Verify the caller is the user
     user.require_auth();
 
+    flexi_deposit_funded(env, user, asset, amount, Funding::Signed)
+}
+
+/// Flexi deposit without the caller check; `funding` selects how the
+/// deposit is pulled from the user's wallet.
+pub(crate) fn flexi_deposit_funded(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    funding: Funding,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(&env)?;
+
     // 2. Validate the amount
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
     }
 
+    assets::require_supported_asset(&env, &asset)?;
+
+    // Interest earned so far accrues on the balance before this deposit
+    settle_flexi_interest(&env, &user, &asset)?;
+
     // 3. Calculate protocol fee
     let fee_bps: u32 = env
         .storage()
@@ -33,7 +62,7 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
         .ok_or(SavingsError::Underflow)?;
 
     // 4. Update the specific Flexi balance with net amount
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
     let current_flexi_balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     let new_flexi_balance = current_flexi_balance
@@ -42,6 +71,8 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
     env.storage()
         .persistent()
         .set(&flexi_key, &new_flexi_balance);
+    add_flexi_asset_to_user(&env, &user, &asset);
+    ledger::adjust(&env, Liability::Flexi, &asset, net_amount)?;
 
     // 5. Sync with the main User struct (Total Balance)
     let user_key = DataKey::User(user.clone());
@@ -61,33 +92,29 @@ pub fn flexi_deposit(env: Env, user: Address, amount: i128) -> Result<(), Saving
     // 6. Award deposit points (streak, rewards)
     rewards::storage::award_deposit_points(&env, user.clone(), amount)?;
 
-    // 7. Transfer fee to treasury if fee > 0
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-            env.events()
-                .publish((symbol_short!("dep_fee"), fee_recipient), fee_amount);
-        }
+    // 7. Credit fee to the fee recipient if fee > 0
+    if let Some(fee_recipient) = fees::accrue_fee(&env, &asset, fee_amount)? {
+        env.events()
+            .publish((symbol_short!("dep_fee"), fee_recipient), fee_amount);
     }
 
+    // 8. Move the deposit into contract custody
+    assets::pull_funded(&env, &asset, &user, amount, funding);
+
     Ok(())
 }
 
-/// Handles withdrawing funds from the Flexi Save pool.
-pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
+/// Handles withdrawing funds from the Flexi Save pool for a given asset.
+///
+/// `amount` is deducted from the Flexi balance; the user receives it net of
+/// the protocol fee, which stays in custody credited to the `FeeRecipient`.
+/// Withdrawals are allowed even if the asset has since left the allowlist.
+pub fn flexi_withdraw(
+    env: Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
     ensure_not_paused(&env)?;
 
     // 1. Verify the caller is the user
@@ -98,8 +125,9 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
         return Err(SavingsError::InvalidAmount);
     }
 
-    // 1. Fetch the balance first
-    let current_balance = get_flexi_balance(&env, user.clone()).unwrap_or(0);
+    // 1. Settle interest accrued so far, then fetch the balance
+    settle_flexi_interest(&env, &user, &asset)?;
+    let current_balance = get_flexi_balance(&env, user.clone(), asset.clone()).unwrap_or(0);
 
     // 2. Now the variable 'current_balance' exists in this scope
     invariants::assert_sufficient_balance(current_balance, amount)?;
@@ -112,12 +140,12 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
         .unwrap_or(0);
 
     let fee_amount = calculate_fee(amount, fee_bps)?;
-    let _net_amount = amount
+    let net_amount = amount
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
     // 4. Check and update the specific Flexi balance
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
     let current_flexi_balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     if current_flexi_balance < amount {
@@ -130,6 +158,7 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
     env.storage()
         .persistent()
         .set(&flexi_key, &new_flexi_balance);
+    ledger::adjust(&env, Liability::Flexi, &asset, -amount)?;
 
     // 5. Sync with the main User struct (Total Balance)
     let user_key = DataKey::User(user.clone());
@@ -144,33 +173,20 @@ pub fn flexi_withdraw(env: Env, user: Address, amount: i128) -> Result<(), Savin
     // Extend TTL on user interaction
     ttl::extend_user_ttl(&env, &user);
 
-    // 6. Transfer fee to treasury if fee > 0
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-            env.events()
-                .publish((symbol_short!("wth_fee"), fee_recipient), fee_amount);
-        }
+    // 6. Credit fee to the fee recipient if fee > 0
+    if let Some(fee_recipient) = fees::accrue_fee(&env, &asset, fee_amount)? {
+        env.events()
+            .publish((symbol_short!("wth_fee"), fee_recipient), fee_amount);
     }
 
+    // 7. Pay the user out of contract custody
+    assets::pay(&env, &asset, &user, net_amount);
+
     Ok(())
 }
-/// Returns the user's Flexi Save balance.
+/// Returns the user's Flexi Save balance in the given asset.
 /// This is a read-only (view) function.
-pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError> {
+pub fn get_flexi_balance(env: &Env, user: Address, asset: Address) -> Result<i128, SavingsError> {
     // 1. Ensure user exists
     let user_key = DataKey::User(user.clone());
     let _user: User = env
@@ -180,7 +196,7 @@ pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError>
         .ok_or(SavingsError::UserNotFound)?;
 
     // 2. Read flexi balance (default to 0)
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset);
     let balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     // Extend TTL on read
@@ -189,10 +205,131 @@ pub fn get_flexi_balance(env: &Env, user: Address) -> Result<i128, SavingsError>
     Ok(balance)
 }
 
-/// Returns true if the user has a non-zero Flexi Save balance.
+/// Brings the user's pending Flexi interest in `asset` up to the current ledger time.
+///
+/// Interest accrues on the stored balance at the current `FlexiRate` for the
+/// time elapsed since the last settlement. Every balance change settles first,
+/// so each period is priced on the balance that was actually held. A rate
+/// change applies from each balance's last settlement onwards.
+pub fn settle_flexi_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+) -> Result<FlexiInterest, SavingsError> {
+    let (interest, earned) = accrue_flexi_interest(env, user, asset)?;
+    if earned > 0 {
+        ledger::adjust(env, Liability::Interest, asset, earned)?;
+    }
+    env.storage().persistent().set(
+        &DataKey::FlexiInterest(user.clone(), asset.clone()),
+        &interest,
+    );
+
+    Ok(interest)
+}
+
+/// Returns the user's Flexi interest in `asset` as of the current ledger time
+/// without settling it. This is a read-only (view) function.
+pub fn pending_flexi_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+) -> Result<FlexiInterest, SavingsError> {
+    Ok(accrue_flexi_interest(env, user, asset)?.0)
+}
+
+/// Brings the stored interest record up to now, returning it with the amount
+/// earned since the last settlement.
+fn accrue_flexi_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+) -> Result<(FlexiInterest, i128), SavingsError> {
+    let now = env.ledger().timestamp();
+    let mut interest: FlexiInterest = env
+        .storage()
+        .persistent()
+        .get(&DataKey::FlexiInterest(user.clone(), asset.clone()))
+        .unwrap_or(FlexiInterest {
+            pending: 0,
+            last_accrual: now,
+        });
+
+    let elapsed = now.saturating_sub(interest.last_accrual);
+    let mut earned = 0;
+    if elapsed > 0 {
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FlexiBalance(user.clone(), asset.clone()))
+            .unwrap_or(0);
+        earned = rates::calculate_flexi_interest(balance, rates::get_flexi_rate(env), elapsed)?;
+        interest.pending = interest
+            .pending
+            .checked_add(earned)
+            .ok_or(SavingsError::Overflow)?;
+    }
+    interest.last_accrual = now;
+
+    Ok((interest, earned))
+}
+
+/// Compounds the user's pending Flexi interest in `asset` into their balance.
+///
+/// # Returns
+/// The amount of interest that was compounded
+pub fn claim_flexi_interest(
+    env: &Env,
+    user: Address,
+    asset: Address,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let user_key = DataKey::User(user.clone());
+    let mut user_data: User = env
+        .storage()
+        .persistent()
+        .get(&user_key)
+        .ok_or(SavingsError::UserNotFound)?;
+
+    let mut interest = settle_flexi_interest(env, &user, &asset)?;
+    let claimed = interest.pending;
+    if claimed == 0 {
+        return Ok(0);
+    }
+
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+    let balance: i128 = env.storage().persistent().get(&flexi_key).unwrap_or(0);
+    let new_balance = balance.checked_add(claimed).ok_or(SavingsError::Overflow)?;
+    env.storage().persistent().set(&flexi_key, &new_balance);
+    ledger::adjust(env, Liability::Interest, &asset, -claimed)?;
+    ledger::adjust(env, Liability::Flexi, &asset, claimed)?;
+
+    interest.pending = 0;
+    env.storage().persistent().set(
+        &DataKey::FlexiInterest(user.clone(), asset.clone()),
+        &interest,
+    );
+
+    user_data.total_balance = user_data
+        .total_balance
+        .checked_add(claimed)
+        .ok_or(SavingsError::Overflow)?;
+    env.storage().persistent().set(&user_key, &user_data);
+
+    ttl::extend_user_ttl(env, &user);
+
+    env.events()
+        .publish((symbol_short!("flx_int"), user, asset), claimed);
+
+    Ok(claimed)
+}
+
+/// Returns true if the user has a non-zero Flexi Save balance in the given asset.
 /// This function does not mutate storage.
-pub fn has_flexi_balance(env: &Env, user: Address) -> bool {
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+pub fn has_flexi_balance(env: &Env, user: Address, asset: Address) -> bool {
+    let flexi_key = DataKey::FlexiBalance(user.clone(), asset);
     let balance = env.storage().persistent().get(&flexi_key).unwrap_or(0i128);
 
     // Extend TTL on read
@@ -201,12 +338,42 @@ pub fn has_flexi_balance(env: &Env, user: Address) -> bool {
     balance > 0
 }
 
+/// Returns every asset the user has held a Flexi balance in.
+pub fn get_user_flexi_assets(env: &Env, user: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserFlexiAssets(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn add_flexi_asset_to_user(env: &Env, user: &Address, asset: &Address) {
+    let mut user_assets = get_user_flexi_assets(env, user);
+    if user_assets.contains(asset) {
+        return;
+    }
+    user_assets.push_back(asset.clone());
+
+    let key = DataKey::UserFlexiAssets(user.clone());
+    env.storage().persistent().set(&key, &user_assets);
+    ttl::extend_user_plan_list_ttl(env, &key);
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{NesteraContract, NesteraContractClient};
-    use soroban_sdk::{testutils::Address as _, Address, Env};
+    use crate::{NesteraContract, NesteraContractClient, SavingsError};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        token::{StellarAssetClient, TokenClient},
+        Address, Env,
+    };
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    fn advance(env: &Env, seconds: u64) {
+        env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
 
-    fn setup_admin_env() -> (Env, NesteraContractClient<'static>, Address) {
+    fn setup_admin_env() -> (Env, NesteraContractClient<'static>, Address, Address) {
         let env = Env::default();
         let contract_id = env.register(NesteraContract, ());
         let client = NesteraContractClient::new(&env, &contract_id);
@@ -216,17 +383,28 @@ mod tests {
         env.mock_all_auths();
         client.initialize(&admin, &admin_pk);
 
-        (env, client, admin)
+        let token_admin = Address::generate(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(token_admin)
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
+        (env, client, admin, token)
+    }
+
+    fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
+        StellarAssetClient::new(env, token).mint(to, &amount);
     }
 
     #[test]
     fn test_flexi_deposit_with_protocol_fee() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
         let treasury = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&500).is_ok()); // 5%
 
@@ -235,16 +413,20 @@ mod tests {
 
         // Net amount = 10,000 - 500 = 9,500
         assert_eq!(client.get_flexi_balance(&user), 9_500);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 500);
+        assert_eq!(
+            client.get_protocol_fee_balance(&treasury, &token).accrued,
+            500
+        );
     }
 
     #[test]
     fn test_flexi_deposit_zero_fee() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
 
         let deposit_amount = 10_000i128;
         client.deposit_flexi(&user, &deposit_amount);
@@ -255,12 +437,13 @@ mod tests {
 
     #[test]
     fn test_flexi_withdraw_with_protocol_fee() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
         let treasury = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&250).is_ok()); // 2.5%
 
@@ -273,16 +456,21 @@ mod tests {
         // Fee: 4,000 * 0.025 = 100
         // Net to user: 3,900
         assert_eq!(client.get_flexi_balance(&user), balance_before - 4_000);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 350); // 250 from deposit + 100 from withdrawal
+        assert_eq!(
+            client.get_protocol_fee_balance(&treasury, &token).accrued,
+            350
+        ); // 250 from deposit + 100 from withdrawal
+        assert_eq!(TokenClient::new(&env, &token).balance(&user), 3_900);
     }
 
     #[test]
     fn test_flexi_withdraw_zero_fee() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
 
         client.deposit_flexi(&user, &10_000);
         client.withdraw_flexi(&user, &3_000);
@@ -292,12 +480,13 @@ mod tests {
 
     #[test]
     fn test_flexi_fee_rounds_down() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
         let treasury = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 3_333);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&125).is_ok()); // 1.25%
 
@@ -306,17 +495,21 @@ mod tests {
         // Fee = floor(3333 * 125 / 10000) = 41
         // Net = 3333 - 41 = 3292
         assert_eq!(client.get_flexi_balance(&user), 3_292);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 41);
+        assert_eq!(
+            client.get_protocol_fee_balance(&treasury, &token).accrued,
+            41
+        );
     }
 
     #[test]
     fn test_flexi_small_amount_edge_case() {
-        let (env, client, _admin) = setup_admin_env();
+        let (env, client, _admin, token) = setup_admin_env();
         let user = Address::generate(&env);
         let treasury = Address::generate(&env);
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        mint(&env, &token, &user, 50);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&100).is_ok()); // 1%
 
@@ -326,6 +519,183 @@ mod tests {
         // Fee = floor(50 * 100 / 10000) = 0
         // Net = 50 - 0 = 50
         assert_eq!(client.get_flexi_balance(&user), 50);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 0);
+        assert_eq!(
+            client.get_protocol_fee_balance(&treasury, &token).accrued,
+            0
+        );
+    }
+
+    #[test]
+    fn test_flexi_deposit_and_withdraw_move_tokens() {
+        let (env, client, _admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+        let token_client = TokenClient::new(&env, &token);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 5_000);
+
+        client.deposit_flexi(&user, &5_000);
+        assert_eq!(token_client.balance(&user), 0);
+        assert_eq!(token_client.balance(&client.address), 5_000);
+
+        client.withdraw_flexi(&user, &2_000);
+        assert_eq!(token_client.balance(&user), 2_000);
+        assert_eq!(token_client.balance(&client.address), 3_000);
+    }
+
+    #[test]
+    fn test_flexi_fee_share_stays_in_custody() {
+        let (env, client, _admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let token_client = TokenClient::new(&env, &token);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
+        assert!(client.try_set_fee_recipient(&treasury).is_ok());
+        assert!(client.try_set_protocol_fee_bps(&1_000).is_ok()); // 10%
+
+        client.deposit_flexi(&user, &10_000);
+        client.withdraw_flexi(&user, &9_000);
+
+        // 1,000 deposit fee + 900 withdrawal fee remain backed by contract tokens
+        assert_eq!(token_client.balance(&user), 8_100);
+        assert_eq!(
+            client.get_protocol_fee_balance(&treasury, &token).accrued,
+            1_900
+        );
+        assert_eq!(token_client.balance(&client.address), 1_900);
+    }
+
+    #[test]
+    fn test_flexi_deposit_without_settlement_asset_fails() {
+        let env = Env::default();
+        let contract_id = env.register(NesteraContract, ());
+        let client = NesteraContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &soroban_sdk::BytesN::from_array(&env, &[1u8; 32]));
+        client.initialize_user(&user);
+
+        assert_eq!(
+            client.try_deposit_flexi(&user, &1_000),
+            Err(Ok(SavingsError::UnsupportedAsset))
+        );
+    }
+
+    #[test]
+    fn test_flexi_deposit_exceeding_wallet_balance_fails() {
+        let (env, client, _admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 999);
+
+        assert!(client.try_deposit_flexi(&user, &1_000).is_err());
+        assert_eq!(client.get_flexi_balance(&user), 0);
+    }
+
+    #[test]
+    fn test_settlement_asset_can_only_be_set_once() {
+        let (env, client, admin, token) = setup_admin_env();
+        let other = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+
+        assert_eq!(
+            client.try_set_settlement_asset(&admin, &other),
+            Err(Ok(SavingsError::ConfigAlreadyInitialized))
+        );
+        assert_eq!(client.get_config().settlement_asset, Some(token));
+    }
+
+    #[test]
+    fn test_flexi_interest_accrues_at_flexi_rate() {
+        let (env, client, admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
+        client.set_flexi_rate(&admin, &500); // 5% APR
+
+        client.deposit_flexi(&user, &10_000);
+        assert_eq!(client.get_flexi_accrued_interest(&user), 0);
+
+        advance(&env, YEAR / 2);
+        assert_eq!(client.get_flexi_accrued_interest(&user), 250);
+
+        advance(&env, YEAR / 2);
+        assert_eq!(client.get_flexi_accrued_interest(&user), 500);
+        // Pending interest is not part of the balance until claimed
+        assert_eq!(client.get_flexi_balance(&user), 10_000);
+    }
+
+    #[test]
+    fn test_flexi_interest_settles_before_balance_changes() {
+        let (env, client, admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 20_000);
+        client.set_flexi_rate(&admin, &1_000); // 10% APR
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR / 2); // 500 on 10,000
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR / 2); // 1,000 on 20,000
+        client.withdraw_flexi(&user, &20_000);
+        advance(&env, YEAR); // nothing on an empty balance
+
+        assert_eq!(client.get_flexi_accrued_interest(&user), 1_500);
+    }
+
+    #[test]
+    fn test_claim_flexi_interest_compounds_into_balance() {
+        let (env, client, admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
+        // Interest is paid out of a reserve held by the contract
+        mint(&env, &token, &client.address, 1_000);
+        client.set_flexi_rate(&admin, &500);
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        assert_eq!(client.claim_flexi_interest(&user), 500);
+        assert_eq!(client.get_flexi_accrued_interest(&user), 0);
+        assert_eq!(client.get_flexi_balance(&user), 10_500);
+        assert_eq!(client.get_user(&user).total_balance, 10_500);
+
+        // A second claim in the same ledger has nothing left to compound
+        assert_eq!(client.claim_flexi_interest(&user), 0);
+
+        client.withdraw_flexi(&user, &10_500);
+        assert_eq!(TokenClient::new(&env, &token).balance(&user), 10_500);
+    }
+
+    #[test]
+    fn test_flexi_interest_is_zero_without_rate() {
+        let (env, client, _admin, token) = setup_admin_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        mint(&env, &token, &user, 10_000);
+
+        client.deposit_flexi(&user, &10_000);
+        advance(&env, YEAR);
+
+        assert_eq!(client.get_flexi_accrued_interest(&user), 0);
+        assert_eq!(client.claim_flexi_interest(&user), 0);
     }
 }
@@ -1,19 +1,30 @@
 use soroban_sdk::{symbol_short, Address, Env, Vec};
 
+use crate::assets::{self, Funding};
 use crate::calculate_fee;
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::fees;
+use crate::ledger::{self, Liability};
+use crate::math::{self, Rounding};
+use crate::rates;
 use crate::rewards::storage;
-use crate::storage_types::{DataKey, GoalSave, User};
+use crate::storage_types::{DataKey, GoalSave};
 use crate::ttl;
 use crate::users;
 
+/// Creates a Goal Save in `asset`, pulling the initial deposit into custody.
+///
+/// `deadline`, if given, is the target date for reaching `target_amount` and
+/// must be in the future. Missing it does not close the goal.
 pub fn create_goal_save(
     env: &Env,
     user: Address,
+    asset: Address,
     goal_name: soroban_sdk::Symbol,
     target_amount: i128,
     initial_deposit: i128,
+    deadline: Option<u64>,
 ) -> Result<u64, SavingsError> {
     ensure_not_paused(env)?;
     user.require_auth();
@@ -26,6 +37,13 @@ pub fn create_goal_save(
         return Err(SavingsError::InvalidAmount);
     }
 
+    let current_time = env.ledger().timestamp();
+    if deadline.is_some_and(|deadline| deadline <= current_time) {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    assets::require_supported_asset(env, &asset)?;
+
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
     }
@@ -42,51 +60,48 @@ pub fn create_goal_save(
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
-    let current_time = env.ledger().timestamp();
+    // The goal rate in force at creation applies for the life of the goal
+    let interest_rate =
+        u32::try_from(rates::get_goal_rate(env)).map_err(|_| SavingsError::InvalidInterestRate)?;
+
     let goal_id = get_next_goal_id(env);
 
-    let goal_save = GoalSave {
+    let mut goal_save = GoalSave {
         id: goal_id,
         owner: user.clone(),
+        asset: asset.clone(),
         goal_name: goal_name.clone(),
         target_amount,
         current_amount: net_initial_deposit,
-        interest_rate: 500,
+        interest_rate,
         start_time: current_time,
+        accrued_interest: 0,
+        last_accrual: current_time,
+        deadline,
+        last_milestone: 0,
+        completed_late: false,
         is_completed: net_initial_deposit >= target_amount,
         is_withdrawn: false,
     };
+    let reached_target = record_milestones(env, &mut goal_save)?;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
 
-    if goal_save.is_completed {
+    ledger::adjust(env, Liability::Goal, &asset, net_initial_deposit)?;
+    assets::pull(env, &asset, &user, initial_deposit);
+
+    if reached_target {
         storage::award_goal_completion_bonus(env, user.clone())?;
     }
 
-    // Transfer fee to treasury if fee > 0
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-            env.events().publish(
-                (symbol_short!("gdep_fee"), fee_recipient, goal_id),
-                fee_amount,
-            );
-        }
+    // Credit fee to the fee recipient if fee > 0
+    if let Some(fee_recipient) = fees::accrue_fee(env, &asset, fee_amount)? {
+        env.events().publish(
+            (symbol_short!("gdep_fee"), fee_recipient, goal_id),
+            fee_amount,
+        );
     }
 
     add_goal_to_user(env, &user, goal_id);
@@ -108,8 +123,20 @@ pub fn deposit_to_goal_save(
     goal_id: u64,
     amount: i128,
 ) -> Result<(), SavingsError> {
-    ensure_not_paused(env)?;
     user.require_auth();
+    deposit_to_goal_save_funded(env, user, goal_id, amount, Funding::Signed)
+}
+
+/// Goal deposit without the caller check; `funding` selects how the deposit
+/// is pulled from the owner's wallet.
+pub(crate) fn deposit_to_goal_save_funded(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+    amount: i128,
+    funding: Funding,
+) -> Result<(), SavingsError> {
+    ensure_not_paused(env)?;
 
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
@@ -121,7 +148,7 @@ pub fn deposit_to_goal_save(
         return Err(SavingsError::Unauthorized);
     }
 
-    if goal_save.is_completed {
+    if goal_save.is_completed || goal_save.is_withdrawn {
         return Err(SavingsError::PlanCompleted);
     }
 
@@ -137,21 +164,28 @@ pub fn deposit_to_goal_save(
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
+    // Settle interest on the existing balance before the new contribution
+    settle_goal_interest(env, &mut goal_save)?;
+
     goal_save.current_amount = goal_save
         .current_amount
         .checked_add(net_amount)
         .ok_or(SavingsError::Overflow)?;
 
-    let was_completed = goal_save.is_completed;
     if goal_save.current_amount >= goal_save.target_amount {
         goal_save.is_completed = true;
     }
+    let reached_target = record_milestones(env, &mut goal_save)?;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
 
-    if !was_completed && goal_save.is_completed {
+    ledger::adjust(env, Liability::Goal, &goal_save.asset, net_amount)?;
+    assets::pull_funded(env, &goal_save.asset, &user, amount, funding);
+
+    // Refilling a goal after a partial withdrawal does not earn the bonus again
+    if reached_target {
         storage::award_goal_completion_bonus(env, user.clone())?;
     }
 
@@ -159,28 +193,12 @@ pub fn deposit_to_goal_save(
     ttl::extend_goal_ttl(env, goal_id);
     ttl::extend_user_ttl(env, &user);
 
-    // Transfer fee to treasury if fee > 0
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-            env.events().publish(
-                (symbol_short!("gdep_fee"), fee_recipient, goal_id),
-                fee_amount,
-            );
-        }
+    // Credit fee to the fee recipient if fee > 0
+    if let Some(fee_recipient) = fees::accrue_fee(env, &goal_save.asset, fee_amount)? {
+        env.events().publish(
+            (symbol_short!("gdep_fee"), fee_recipient, goal_id),
+            fee_amount,
+        );
     }
 
     // Award deposit points
@@ -189,6 +207,8 @@ pub fn deposit_to_goal_save(
     Ok(())
 }
 
+/// Pays out a completed goal: principal minus the withdrawal fee, plus the
+/// interest accrued at the goal's rate.
 pub fn withdraw_completed_goal_save(
     env: &Env,
     user: Address,
@@ -222,11 +242,15 @@ pub fn withdraw_completed_goal_save(
         .get(&DataKey::PlatformFee)
         .unwrap_or(0);
 
+    // The fee applies to principal only; interest is paid out in full
     let fee_amount = calculate_fee(goal_save.current_amount, fee_bps)?;
+    settle_goal_interest(env, &mut goal_save)?;
     let net_amount = goal_save
         .current_amount
         .checked_sub(fee_amount)
-        .ok_or(SavingsError::Underflow)?;
+        .ok_or(SavingsError::Underflow)?
+        .checked_add(goal_save.accrued_interest)
+        .ok_or(SavingsError::Overflow)?;
 
     goal_save.is_withdrawn = true;
 
@@ -234,46 +258,46 @@ pub fn withdraw_completed_goal_save(
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
 
-    let user_key = DataKey::User(user.clone());
-    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
-        user_data.total_balance = user_data
-            .total_balance
-            .checked_add(net_amount)
-            .ok_or(SavingsError::Overflow)?;
-        env.storage().persistent().set(&user_key, &user_data);
+    ledger::adjust(
+        env,
+        Liability::Goal,
+        &goal_save.asset,
+        -goal_save.current_amount,
+    )?;
+    ledger::adjust(
+        env,
+        Liability::Interest,
+        &goal_save.asset,
+        -goal_save.accrued_interest,
+    )?;
+
+    // Pay the user out of contract custody; the fee share stays behind
+    assets::pay(env, &goal_save.asset, &user, net_amount);
+
+    if goal_save.accrued_interest > 0 {
+        env.events().publish(
+            (symbol_short!("goal_int"), user.clone(), goal_id),
+            goal_save.accrued_interest,
+        );
     }
 
     // Extend TTL (withdrawn goals get shorter extension)
     ttl::extend_goal_ttl(env, goal_id);
     ttl::extend_user_ttl(env, &user);
 
-    // Transfer fee to treasury if fee > 0
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-            env.events().publish(
-                (symbol_short!("gwth_fee"), fee_recipient, goal_id),
-                fee_amount,
-            );
-        }
+    // Credit fee to the fee recipient if fee > 0
+    if let Some(fee_recipient) = fees::accrue_fee(env, &goal_save.asset, fee_amount)? {
+        env.events().publish(
+            (symbol_short!("gwth_fee"), fee_recipient, goal_id),
+            fee_amount,
+        );
     }
 
     Ok(net_amount)
 }
 
+/// Closes an incomplete goal early, returning principal minus the early
+/// break fee. Interest accrued on the goal is forfeited.
 pub fn break_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<i128, SavingsError> {
     ensure_not_paused(env)?;
     user.require_auth();
@@ -306,61 +330,41 @@ pub fn break_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<i128, S
         return Err(SavingsError::InvalidAmount);
     }
 
-    let fee_amount = if fee_bps == 0 {
-        0
-    } else {
-        goal_save
-            .current_amount
-            .checked_mul(fee_bps as i128)
-            .ok_or(SavingsError::Overflow)?
-            / 10_000
-    };
+    let fee_amount = math::apply_bps(goal_save.current_amount, fee_bps as i128, Rounding::Down)?;
 
     let net_amount = goal_save
         .current_amount
         .checked_sub(fee_amount)
         .ok_or(SavingsError::Underflow)?;
 
+    // Breaking early forfeits any interest accrued so far
+    ledger::adjust(
+        env,
+        Liability::Interest,
+        &goal_save.asset,
+        -goal_save.accrued_interest,
+    )?;
+    ledger::adjust(
+        env,
+        Liability::Goal,
+        &goal_save.asset,
+        -goal_save.current_amount,
+    )?;
+    goal_save.accrued_interest = 0;
     goal_save.is_withdrawn = true;
 
     env.storage()
         .persistent()
         .set(&DataKey::GoalSave(goal_id), &goal_save);
 
-    let user_key = DataKey::User(user.clone());
-    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
-        user_data.total_balance = user_data
-            .total_balance
-            .checked_add(net_amount)
-            .ok_or(SavingsError::Overflow)?;
-        env.storage().persistent().set(&user_key, &user_data);
-    }
-
-    if fee_amount > 0 {
-        if let Some(fee_recipient) = env
-            .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::FeeRecipient)
-        {
-            let fee_key = DataKey::TotalBalance(fee_recipient.clone());
-            let current_fee_balance = env
-                .storage()
-                .persistent()
-                .get::<DataKey, i128>(&fee_key)
-                .unwrap_or(0i128);
-            let new_fee_balance = current_fee_balance
-                .checked_add(fee_amount)
-                .ok_or(SavingsError::Overflow)?;
-            env.storage().persistent().set(&fee_key, &new_fee_balance);
-
-            // Extend TTL on fee storage
-            ttl::extend_config_ttl(env, &fee_key);
+    // Pay the user out of contract custody; the fee share stays behind
+    assets::pay(env, &goal_save.asset, &user, net_amount);
 
-            env.events().publish(
-                (symbol_short!("brk_fee"), fee_recipient, goal_id),
-                fee_amount,
-            );
-        }
+    if let Some(fee_recipient) = fees::accrue_fee(env, &goal_save.asset, fee_amount)? {
+        env.events().publish(
+            (symbol_short!("brk_fee"), fee_recipient, goal_id),
+            fee_amount,
+        );
     }
 
     env.events().publish(
@@ -377,6 +381,183 @@ pub fn break_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<i128, S
     Ok(net_amount)
 }
 
+/// Withdraws part of a goal's principal while keeping the goal open.
+///
+/// `EarlyBreakFeeBps` is charged on the withdrawn `amount` only. Interest
+/// accrued so far stays with the goal, and a goal that drops below its
+/// target is marked incomplete again.
+pub fn partial_withdraw_goal_save(
+    env: &Env,
+    user: Address,
+    goal_id: u64,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut goal_save = get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if goal_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if goal_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    if amount > goal_save.current_amount {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    let fee_bps: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::EarlyBreakFeeBps)
+        .unwrap_or(0);
+
+    if fee_bps > 10_000 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let fee_amount = math::apply_bps(amount, fee_bps as i128, Rounding::Down)?;
+    let net_amount = amount
+        .checked_sub(fee_amount)
+        .ok_or(SavingsError::Underflow)?;
+
+    // Settle interest on the full balance before it shrinks
+    settle_goal_interest(env, &mut goal_save)?;
+
+    goal_save.current_amount = goal_save
+        .current_amount
+        .checked_sub(amount)
+        .ok_or(SavingsError::Underflow)?;
+    if goal_save.current_amount < goal_save.target_amount {
+        goal_save.is_completed = false;
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::GoalSave(goal_id), &goal_save);
+
+    ledger::adjust(env, Liability::Goal, &goal_save.asset, -amount)?;
+
+    // Pay the user out of contract custody; the fee share stays behind
+    assets::pay(env, &goal_save.asset, &user, net_amount);
+
+    if let Some(fee_recipient) = fees::accrue_fee(env, &goal_save.asset, fee_amount)? {
+        env.events().publish(
+            (symbol_short!("brk_fee"), fee_recipient, goal_id),
+            fee_amount,
+        );
+    }
+
+    env.events().publish(
+        (symbol_short!("goal_pwd"), user.clone(), goal_id),
+        (net_amount, goal_save.current_amount),
+    );
+
+    ttl::extend_goal_ttl(env, goal_id);
+    ttl::extend_user_ttl(env, &user);
+
+    Ok(net_amount)
+}
+
+/// Percentages of the target that emit a `goal_mile` event when first reached.
+const GOAL_MILESTONES: [u32; 4] = [25, 50, 75, 100];
+
+/// Emits an event for each milestone the goal has newly passed.
+///
+/// Milestones are only ever reached once, so a partial withdrawal followed by
+/// a refill does not repeat them. Returns true if the goal has just reached
+/// 100% for the first time, flagging it as late if that is past the deadline.
+fn record_milestones(env: &Env, goal_save: &mut GoalSave) -> Result<bool, SavingsError> {
+    let progress = if goal_save.current_amount >= goal_save.target_amount {
+        100
+    } else {
+        // current_amount < target_amount, so this stays below 100
+        math::mul_div(
+            goal_save.current_amount.max(0),
+            100,
+            goal_save.target_amount,
+            Rounding::Down,
+        )? as u32
+    };
+
+    let previous = goal_save.last_milestone;
+    for milestone in GOAL_MILESTONES {
+        if milestone > previous && progress >= milestone {
+            goal_save.last_milestone = milestone;
+            env.events().publish(
+                (
+                    symbol_short!("goal_mile"),
+                    goal_save.owner.clone(),
+                    goal_save.id,
+                ),
+                milestone,
+            );
+        }
+    }
+
+    let reached_target = previous < 100 && goal_save.last_milestone == 100;
+    if reached_target {
+        let now = env.ledger().timestamp();
+        goal_save.completed_late = goal_save.deadline.is_some_and(|deadline| now > deadline);
+    }
+    Ok(reached_target)
+}
+
+/// True if the goal's deadline passed before it reached its target.
+pub fn missed_deadline(env: &Env, goal_save: &GoalSave) -> bool {
+    if goal_save.last_milestone == 100 {
+        return goal_save.completed_late;
+    }
+    goal_save
+        .deadline
+        .is_some_and(|deadline| env.ledger().timestamp() > deadline)
+}
+
+/// Returns the goal's interest accrued up to now without writing it back.
+pub fn get_accrued_goal_interest(env: &Env, goal_save: &GoalSave) -> Result<i128, SavingsError> {
+    if goal_save.is_withdrawn {
+        return Ok(goal_save.accrued_interest);
+    }
+    let elapsed = env
+        .ledger()
+        .timestamp()
+        .saturating_sub(goal_save.last_accrual);
+    let earned = math::simple_interest(
+        goal_save.current_amount,
+        goal_save.interest_rate as i128,
+        elapsed,
+        Rounding::Down,
+    )?;
+    goal_save
+        .accrued_interest
+        .checked_add(earned)
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Brings `accrued_interest` up to the current ledger time.
+///
+/// Settling whenever the balance changes makes accrual time-weighted: each
+/// contribution earns interest only from the moment it was deposited.
+fn settle_goal_interest(env: &Env, goal_save: &mut GoalSave) -> Result<(), SavingsError> {
+    let accrued = get_accrued_goal_interest(env, goal_save)?;
+    ledger::adjust(
+        env,
+        Liability::Interest,
+        &goal_save.asset,
+        accrued - goal_save.accrued_interest,
+    )?;
+    goal_save.accrued_interest = accrued;
+    goal_save.last_accrual = env.ledger().timestamp();
+    Ok(())
+}
+
 pub fn get_goal_save(env: &Env, goal_id: u64) -> Option<GoalSave> {
     let goal_save = env.storage().persistent().get(&DataKey::GoalSave(goal_id));
     if goal_save.is_some() {
@@ -453,14 +634,17 @@ mod tests {
     use crate::rewards::storage_types::RewardsConfig;
     use crate::{NesteraContract, NesteraContractClient};
     use soroban_sdk::{
-        testutils::{Address as _, Events},
-        Address, BytesN, Env, IntoVal, Symbol,
+        testutils::{Address as _, Events, Ledger},
+        token::{StellarAssetClient, TokenClient},
+        Address, Env, IntoVal, Symbol, TryIntoVal,
     };
+    extern crate std;
+    use std::vec::Vec;
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
 
     fn setup_test_env() -> (Env, NesteraContractClient<'static>) {
-        let env = Env::default();
-        let contract_id = env.register(NesteraContract, ());
-        let client = NesteraContractClient::new(&env, &contract_id);
+        let (env, client, _admin) = setup_admin_env();
         (env, client)
     }
 
@@ -474,20 +658,32 @@ mod tests {
         env.mock_all_auths();
         client.initialize(&admin, &admin_pk);
 
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
         (env, client, admin)
     }
 
+    /// Mints settlement asset tokens to `user` so goal deposits can settle
+    fn fund(env: &Env, client: &NesteraContractClient, user: &Address) {
+        let token = client.get_config().settlement_asset.unwrap();
+        StellarAssetClient::new(env, &token).mint(user, &1_000_000);
+    }
+
+    fn fee_accrued(client: &NesteraContractClient<'_>, treasury: &Address) -> i128 {
+        let asset = client.get_config().settlement_asset.unwrap();
+        client.get_protocol_fee_balance(treasury, &asset).accrued
+    }
+
     fn setup_rewards_with(
         client: &NesteraContractClient<'_>,
         env: &Env,
         enabled: bool,
         completion_bonus: u32,
     ) {
-        let admin = Address::generate(env);
-        let admin_pk = BytesN::from_array(env, &[2u8; 32]);
-
         env.mock_all_auths();
-        client.initialize(&admin, &admin_pk);
 
         let config = RewardsConfig {
             points_per_token: 10,
@@ -556,12 +752,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "vacation");
         let target = 10000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         assert_eq!(goal_id, 1);
 
         let goal_save = client.get_goal_save_detail(&goal_id);
@@ -579,12 +776,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "house");
         let target = 5000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         client.deposit_to_goal_save(&user, &goal_id, &2000);
 
         let goal_save = client.get_goal_save_detail(&goal_id);
@@ -599,12 +797,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "laptop");
         let target = 5000i128;
         let initial = 3000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         client.deposit_to_goal_save(&user, &goal_id, &2000);
 
         let goal_save = client.get_goal_save_detail(&goal_id);
@@ -619,12 +818,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "car");
         let target = 1000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
 
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert!(goal_save.is_completed);
@@ -644,12 +844,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "bike");
         let target = 5000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
 
         client.withdraw_completed_goal_save(&user, &goal_id);
     }
@@ -662,12 +863,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "fund");
         let target = 1000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         client.withdraw_completed_goal_save(&user, &goal_id);
         client.withdraw_completed_goal_save(&user, &goal_id);
     }
@@ -681,13 +883,15 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user1);
+        fund(&env, &client, &user1);
         client.initialize_user(&user2);
+        fund(&env, &client, &user2);
 
         let goal_name = Symbol::new(&env, "test");
         let target = 1000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user1, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user1, &goal_name, &target, &initial, &None);
         client.withdraw_completed_goal_save(&user2, &goal_id);
     }
 
@@ -698,12 +902,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "emergency");
         let target = 5000i128;
         let initial = 2000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         let net_amount = client.break_goal_save(&user, &goal_id);
         assert_eq!(net_amount, initial);
 
@@ -722,12 +927,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "done");
         let target = 1000i128;
         let initial = 1000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         client.break_goal_save(&user, &goal_id);
     }
 
@@ -739,6 +945,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_early_break_fee_bps(&500).is_ok()); // 5%
 
@@ -746,11 +953,11 @@ mod tests {
         let target = 10_000i128;
         let initial = 2_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         let net_amount = client.break_goal_save(&user, &goal_id);
 
         assert_eq!(net_amount, 1_900);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 100);
+        assert_eq!(fee_accrued(&client, &treasury), 100);
     }
 
     #[test]
@@ -761,6 +968,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_early_break_fee_bps(&125).is_ok()); // 1.25%
 
@@ -768,12 +976,82 @@ mod tests {
         let target = 10_000i128;
         let initial = 3_333i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         let net_amount = client.break_goal_save(&user, &goal_id);
 
         // fee = floor(3333 * 125 / 10000) = 41
         assert_eq!(net_amount, 3_292);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 41);
+        assert_eq!(fee_accrued(&client, &treasury), 41);
+    }
+
+    #[test]
+    fn test_partial_withdraw_charges_fee_on_portion_and_reopens_goal() {
+        let (env, client, _admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        let treasury = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        assert!(client.try_set_fee_recipient(&treasury).is_ok());
+        assert!(client.try_set_early_break_fee_bps(&500).is_ok()); // 5%
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let goal_name = Symbol::new(&env, "house");
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &6_000, &None);
+        assert!(client.get_goal_save_detail(&goal_id).is_completed);
+
+        // 5% of the 2_000 withdrawn, not of the whole balance
+        let net_amount = client.partial_withdraw_goal_save(&user, &goal_id, &2_000);
+        assert_eq!(
+            env.events().all().last().unwrap().1,
+            (Symbol::new(&env, "goal_pwd"), user.clone(), goal_id).into_val(&env)
+        );
+        assert_eq!(net_amount, 1_900);
+        assert_eq!(fee_accrued(&client, &treasury), 100);
+        assert_eq!(token.balance(&user), 1_000_000 - 6_000 + 1_900);
+
+        let goal = client.get_goal_save_detail(&goal_id);
+        assert_eq!(goal.current_amount, 4_000);
+        assert!(!goal.is_completed);
+        assert!(!goal.is_withdrawn);
+        assert_eq!(client.get_user_goal_saves(&user).len(), 1);
+        assert_eq!(client.get_solvency_report(&token.address).goal, 4_000);
+
+        // The goal stays open for further deposits
+        client.deposit_to_goal_save(&user, &goal_id, &1_000);
+        assert!(client.get_goal_save_detail(&goal_id).is_completed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #40)")]
+    fn test_partial_withdraw_more_than_balance_fails() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let goal_name = Symbol::new(&env, "bike");
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &1_000, &None);
+        client.partial_withdraw_goal_save(&user, &goal_id, &1_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #23)")]
+    fn test_deposit_to_broken_goal_fails() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let goal_name = Symbol::new(&env, "broken");
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &1_000, &None);
+        client.break_goal_save(&user, &goal_id);
+        client.deposit_to_goal_save(&user, &goal_id, &2_000);
     }
 
     #[test]
@@ -785,13 +1063,15 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user1);
+        fund(&env, &client, &user1);
         client.initialize_user(&user2);
+        fund(&env, &client, &user2);
 
         let goal_name = Symbol::new(&env, "other");
         let target = 5000i128;
         let initial = 2000i128;
 
-        let goal_id = client.create_goal_save(&user1, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user1, &goal_name, &target, &initial, &None);
         client.break_goal_save(&user2, &goal_id);
     }
 
@@ -803,12 +1083,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "invalid");
         let target = 0i128;
         let initial = 100i128;
 
-        client.create_goal_save(&user, &goal_name, &target, &initial);
+        client.create_goal_save(&user, &goal_name, &target, &initial, &None);
     }
 
     #[test]
@@ -823,7 +1104,7 @@ mod tests {
         let target = 5000i128;
         let initial = 1000i128;
 
-        client.create_goal_save(&user, &goal_name, &target, &initial);
+        client.create_goal_save(&user, &goal_name, &target, &initial, &None);
     }
 
     #[test]
@@ -834,6 +1115,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&500).is_ok()); // 5%
 
@@ -841,12 +1123,12 @@ mod tests {
         let target = 10_000i128;
         let initial = 5_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
 
         let goal_save = client.get_goal_save_detail(&goal_id);
         // Net = 5,000 - 250 = 4,750
         assert_eq!(goal_save.current_amount, 4_750);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 250);
+        assert_eq!(fee_accrued(&client, &treasury), 250);
     }
 
     #[test]
@@ -857,6 +1139,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&300).is_ok()); // 3%
 
@@ -864,9 +1147,9 @@ mod tests {
         let target = 10_000i128;
         let initial = 2_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         // Initial: 2,000 - 60 = 1,940
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 60);
+        assert_eq!(fee_accrued(&client, &treasury), 60);
 
         client.deposit_to_goal_save(&user, &goal_id, &3_000);
         // Deposit: 3,000 - 90 = 2,910
@@ -875,7 +1158,7 @@ mod tests {
 
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert_eq!(goal_save.current_amount, 4_850);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 150);
+        assert_eq!(fee_accrued(&client, &treasury), 150);
     }
 
     #[test]
@@ -886,6 +1169,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&250).is_ok()); // 2.5%
 
@@ -893,18 +1177,18 @@ mod tests {
         let target = 4_000i128;
         let initial = 5_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         // Initial: 5,000 - 125 = 4,875 (exceeds target of 4,000, so completed)
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert_eq!(goal_save.current_amount, 4_875);
         assert!(goal_save.is_completed);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 125);
+        assert_eq!(fee_accrued(&client, &treasury), 125);
 
         let amount = client.withdraw_completed_goal_save(&user, &goal_id);
         // Withdrawal: 4,875 - 121 = 4,754 (fee rounded down)
         assert_eq!(amount, 4_754);
         // Total fees: 125 + 121 = 246
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 246);
+        assert_eq!(fee_accrued(&client, &treasury), 246);
     }
 
     #[test]
@@ -914,12 +1198,13 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "car");
         let target = 5_000i128;
         let initial = 5_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert_eq!(goal_save.current_amount, 5_000);
         assert!(goal_save.is_completed);
@@ -936,6 +1221,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&1000).is_ok()); // 10%
 
@@ -943,12 +1229,12 @@ mod tests {
         let target = 10_000i128;
         let initial = 1_000i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         // Fee = 1,000 * 10% = 100
         // Net = 900
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert_eq!(goal_save.current_amount, 900);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 100);
+        assert_eq!(fee_accrued(&client, &treasury), 100);
     }
 
     #[test]
@@ -959,6 +1245,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
         assert!(client.try_set_fee_recipient(&treasury).is_ok());
         assert!(client.try_set_protocol_fee_bps(&100).is_ok()); // 1%
 
@@ -966,12 +1253,78 @@ mod tests {
         let target = 1_000i128;
         let initial = 50i128;
 
-        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial);
+        let goal_id = client.create_goal_save(&user, &goal_name, &target, &initial, &None);
         // Fee = floor(50 * 100 / 10000) = 0
         // Net = 50
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert_eq!(goal_save.current_amount, 50);
-        assert_eq!(client.get_protocol_fee_balance(&treasury), 0);
+        assert_eq!(fee_accrued(&client, &treasury), 0);
+    }
+
+    /// Goal rate of 10% with a reserve in custody to pay interest from
+    fn setup_goal_interest() -> (Env, NesteraContractClient<'static>, Address) {
+        let (env, client, admin) = setup_admin_env();
+        client.set_goal_rate(&admin, &1_000);
+        let token = client.get_config().settlement_asset.unwrap();
+        StellarAssetClient::new(&env, &token).mint(&client.address, &1_000_000);
+
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        (env, client, user)
+    }
+
+    fn advance(env: &Env, seconds: u64) {
+        env.ledger().with_mut(|li| li.timestamp += seconds);
+    }
+
+    #[test]
+    fn test_goal_interest_is_time_weighted_across_deposits() {
+        let (env, client, user) = setup_goal_interest();
+        let goal_id =
+            client.create_goal_save(&user, &Symbol::new(&env, "trip"), &10_000, &1_000, &None);
+        assert_eq!(client.get_goal_save_detail(&goal_id).interest_rate, 1_000);
+
+        advance(&env, YEAR / 2);
+        client.deposit_to_goal_save(&user, &goal_id, &1_000);
+        advance(&env, YEAR / 2);
+
+        // 1,000 for a full year plus 1,000 for half a year at 10%
+        let view = client.get_goal_save_view(&goal_id);
+        assert_eq!(view.balance, 2_000);
+        assert_eq!(view.accrued_interest, 150);
+    }
+
+    #[test]
+    fn test_goal_interest_paid_on_completed_withdrawal() {
+        let (env, client, user) = setup_goal_interest();
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+        let goal_id =
+            client.create_goal_save(&user, &Symbol::new(&env, "bike"), &2_000, &1_000, &None);
+
+        advance(&env, YEAR);
+        client.deposit_to_goal_save(&user, &goal_id, &1_000);
+        advance(&env, YEAR / 2);
+
+        let before = token.balance(&user);
+        // 100 on the first 1,000 over a year, then 100 on 2,000 for half a year
+        assert_eq!(client.withdraw_completed_goal_save(&user, &goal_id), 2_200);
+        assert_eq!(token.balance(&user), before + 2_200);
+        assert_eq!(client.get_goal_save_view(&goal_id).accrued_interest, 200);
+    }
+
+    #[test]
+    fn test_break_goal_save_forfeits_interest() {
+        let (env, client, user) = setup_goal_interest();
+        let goal_id =
+            client.create_goal_save(&user, &Symbol::new(&env, "fund"), &10_000, &1_000, &None);
+
+        advance(&env, YEAR);
+        client.deposit_to_goal_save(&user, &goal_id, &1_000);
+        advance(&env, YEAR);
+
+        assert_eq!(client.break_goal_save(&user, &goal_id), 2_000);
+        assert_eq!(client.get_goal_save_view(&goal_id).accrued_interest, 0);
     }
 
     #[test]
@@ -982,9 +1335,10 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "bonusgoal");
-        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &4_000);
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &4_000, &None);
 
         client.deposit_to_goal_save(&user, &goal_id, &1_000);
         let rewards_after_completion = client.get_user_rewards(&user);
@@ -1005,9 +1359,10 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "nobonus");
-        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &4_999);
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &4_999, &None);
         let goal_save = client.get_goal_save_detail(&goal_id);
         assert!(!goal_save.is_completed);
 
@@ -1024,9 +1379,10 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "instant");
-        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &5_000);
+        let goal_id = client.create_goal_save(&user, &goal_name, &5_000, &5_000, &None);
         let goal = client.get_goal_save_detail(&goal_id);
         assert!(goal.is_completed);
 
@@ -1044,9 +1400,10 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "disabled");
-        let _goal_id = client.create_goal_save(&user, &goal_name, &5_000, &5_000);
+        let _goal_id = client.create_goal_save(&user, &goal_name, &5_000, &5_000, &None);
 
         let rewards = client.get_user_rewards(&user);
         assert_eq!(rewards.total_points, 0);
@@ -1060,13 +1417,150 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let goal_name = Symbol::new(&env, "breakcase");
-        let goal_id = client.create_goal_save(&user, &goal_name, &10_000, &2_000);
+        let goal_id = client.create_goal_save(&user, &goal_name, &10_000, &2_000, &None);
         let _ = client.break_goal_save(&user, &goal_id);
 
         let rewards = client.get_user_rewards(&user);
         // Base points: 2000 * 10 = 20000
         assert_eq!(rewards.total_points, 20000);
     }
+
+    fn milestones_emitted(env: &Env) -> Vec<u32> {
+        let mut milestones = Vec::new();
+        for (_, topics, data) in env.events().all().iter() {
+            let name: Result<Symbol, _> = topics.get(0).unwrap().try_into_val(env);
+            if name == Ok(Symbol::new(env, "goal_mile")) {
+                milestones.push(data.try_into_val(env).unwrap());
+            }
+        }
+        milestones
+    }
+
+    #[test]
+    fn test_goal_milestones_are_emitted_once() {
+        let (env, client, _admin) = setup_admin_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        setup_rewards(&client, &env);
+
+        let goal_name = Symbol::new(&env, "laptop");
+        let goal_id = client.create_goal_save(&user, &goal_name, &1_000, &300, &None);
+        assert_eq!(milestones_emitted(&env), [25]);
+
+        client.deposit_to_goal_save(&user, &goal_id, &500);
+        assert_eq!(milestones_emitted(&env), [50, 75]);
+
+        client.deposit_to_goal_save(&user, &goal_id, &200);
+        assert_eq!(milestones_emitted(&env), [100]);
+        let points = client.get_user_rewards(&user).total_points;
+
+        // Dropping below target and refilling repeats neither milestones nor bonus
+        client.partial_withdraw_goal_save(&user, &goal_id, &600);
+        client.deposit_to_goal_save(&user, &goal_id, &600);
+        assert!(milestones_emitted(&env).is_empty());
+        assert!(client.get_goal_save_detail(&goal_id).is_completed);
+        assert_eq!(client.get_user_rewards(&user).total_points, points + 6_000);
+    }
+
+    #[test]
+    fn test_goal_milestones_on_very_large_balances() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        let tenth = i128::MAX / 10;
+        let token = client.get_config().settlement_asset.unwrap();
+        StellarAssetClient::new(&env, &token).mint(&user, &(2 * tenth));
+
+        let goal_name = Symbol::new(&env, "moon");
+        let goal_id = client.create_goal_save(&user, &goal_name, &(i128::MAX / 2), &tenth, &None);
+        assert!(milestones_emitted(&env).is_empty());
+        client.deposit_to_goal_save(&user, &goal_id, &tenth);
+        assert_eq!(milestones_emitted(&env), [25]);
+    }
+
+    #[test]
+    fn test_goal_completed_after_deadline_is_marked_late() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let deadline = env.ledger().timestamp() + YEAR;
+        let goal_name = Symbol::new(&env, "wedding");
+        let goal_id = client.create_goal_save(&user, &goal_name, &10_000, &1_000, &Some(deadline));
+        assert!(!client.get_goal_save_view(&goal_id).missed_deadline);
+
+        env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+        let view = client.get_goal_save_view(&goal_id);
+        assert_eq!(view.deadline, Some(deadline));
+        assert!(view.missed_deadline);
+        assert!(!view.is_completed);
+
+        // The goal can still be completed and withdrawn, flagged as late
+        client.deposit_to_goal_save(&user, &goal_id, &9_000);
+        let goal = client.get_goal_save_detail(&goal_id);
+        assert!(goal.is_completed);
+        assert!(goal.completed_late);
+        assert!(client.get_goal_save_view(&goal_id).missed_deadline);
+        assert_eq!(client.withdraw_completed_goal_save(&user, &goal_id), 10_000);
+    }
+
+    #[test]
+    fn test_required_contribution_spreads_shortfall_to_deadline() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let month = 30 * 24 * 60 * 60;
+        let deadline = env.ledger().timestamp() + 10 * month;
+        let goal_name = Symbol::new(&env, "school");
+        let goal_id = client.create_goal_save(&user, &goal_name, &10_000, &1_000, &Some(deadline));
+
+        // 9_000 short over ten months
+        assert_eq!(client.get_goal_required_contribution(&goal_id, &month), 900);
+
+        // A 1% deposit fee is grossed up: ceil(900 * 10_000 / 9_900)
+        client.set_protocol_fee_bps(&100);
+        assert_eq!(client.get_goal_required_contribution(&goal_id, &month), 910);
+        client.set_protocol_fee_bps(&0);
+
+        // Part-way through a period counts as a whole period left
+        env.ledger()
+            .with_mut(|li| li.timestamp += 7 * month + month / 2);
+        assert_eq!(
+            client.get_goal_required_contribution(&goal_id, &month),
+            3_000
+        );
+
+        // Past the deadline the whole shortfall is due now
+        env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+        assert_eq!(
+            client.get_goal_required_contribution(&goal_id, &month),
+            9_000
+        );
+
+        let open_goal = client.create_goal_save(&user, &goal_name, &10_000, &1_000, &None);
+        assert_eq!(
+            client.try_get_goal_required_contribution(&open_goal, &month),
+            Err(Ok(crate::SavingsError::InvalidPlanConfig))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #50)")]
+    fn test_create_goal_save_past_deadline_fails() {
+        let (env, client) = setup_test_env();
+        let user = Address::generate(&env);
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let now = env.ledger().timestamp();
+        let goal_name = Symbol::new(&env, "late");
+        client.create_goal_save(&user, &goal_name, &10_000, &1_000, &Some(now));
+    }
 }
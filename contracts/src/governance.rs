@@ -63,6 +63,12 @@ pub enum ProposalAction {
     SetLockRate(u64, i128),
     PauseContract,
     UnpauseContract,
+    /// (asset, strategy, max_allocation_bps, buffer_bps)
+    SetStrategy(Address, Address, u32, u32),
+    /// (asset, amount)
+    AllocateToStrategy(Address, i128),
+    /// (asset, amount)
+    RecallFromStrategy(Address, i128),
 }
 
 /// Calculates voting power for a user based on their lifetime deposited funds
@@ -579,13 +585,7 @@ fn execute_action(env: &Env, action: &ProposalAction) -> Result<(), SavingsError
             Ok(())
         }
         ProposalAction::SetLockRate(duration, rate) => {
-            if *rate < 0 {
-                return Err(SavingsError::InvalidInterestRate);
-            }
-            env.storage()
-                .instance()
-                .set(&DataKey::LockRate(*duration), rate);
-            Ok(())
+            crate::rates::store_lock_rate(env, *duration, *rate)
         }
         ProposalAction::PauseContract => {
             env.storage().persistent().set(&DataKey::Paused, &true);
@@ -597,6 +597,21 @@ fn execute_action(env: &Env, action: &ProposalAction) -> Result<(), SavingsError
             crate::ttl::extend_config_ttl(env, &DataKey::Paused);
             Ok(())
         }
+        ProposalAction::SetStrategy(asset, strategy, max_allocation_bps, buffer_bps) => {
+            crate::strategy::store_strategy(
+                env,
+                asset.clone(),
+                strategy.clone(),
+                *max_allocation_bps,
+                *buffer_bps,
+            )
+        }
+        ProposalAction::AllocateToStrategy(asset, amount) => {
+            crate::strategy::allocate(env, asset, *amount)
+        }
+        ProposalAction::RecallFromStrategy(asset, amount) => {
+            crate::strategy::recall(env, asset, *amount).map(|_| ())
+        }
     }
 }
 
@@ -1,10 +1,24 @@
+use crate::assets::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
-use crate::storage_types::{DataKey, GroupSave};
+use crate::invites;
+use crate::ledger::{self, Liability};
+use crate::math::{self, Rounding};
+use crate::rates;
+use crate::rosca;
+use crate::storage_types::{DataKey, GroupDataKey, GroupMemberAccrual, GroupSave};
 use crate::ttl;
 use crate::users;
 use soroban_sdk::{Address, Env, String, Vec};
 
+/// Members contribute exactly `contribution_amount` once per period
+pub const CONTRIBUTION_FIXED: u32 = 0;
+/// Members contribute at least `contribution_amount` at a time
+pub const CONTRIBUTION_FLEXIBLE: u32 = 1;
+/// Members contribute their share of the target, the target divided by the
+/// member count, once per period
+pub const CONTRIBUTION_PERCENTAGE: u32 = 2;
+
 /// Creates a new group savings plan.
 ///
 /// The creator is automatically added as the first member (member_count = 1).
@@ -13,12 +27,14 @@ use soroban_sdk::{Address, Env, String, Vec};
 /// # Arguments
 /// * `env` - The contract environment
 /// * `creator` - The address of the user creating the group
+/// * `asset` - The asset contract members contribute in
 /// * `title` - Title/name of the group savings plan
 /// * `description` - Description of the group savings goal
 /// * `category` - Category of the group savings (e.g., "education", "emergency")
 /// * `target_amount` - Target amount to save (must be > 0)
-/// * `contribution_type` - Type of contribution (0 = fixed, 1 = flexible, etc.)
-/// * `contribution_amount` - Contribution amount or minimum (must be > 0)
+/// * `contribution_type` - Type of contribution (0 = fixed, 1 = flexible, 2 = percentage)
+/// * `contribution_amount` - Fixed amount or flexible minimum (must be > 0);
+///   ignored by percentage groups, whose share comes from the target
 /// * `is_public` - Whether the group is public or private
 /// * `start_time` - Unix timestamp when the group starts
 /// * `end_time` - Unix timestamp when the group ends (must be > start_time)
@@ -31,9 +47,14 @@ use soroban_sdk::{Address, Env, String, Vec};
 /// * `InvalidAmount` - If target_amount or contribution_amount <= 0
 /// * `InvalidTimestamp` - If start_time >= end_time
 /// * `InvalidGroupConfig` - If other parameters are invalid
+/// * `UnsupportedAsset` - If the asset is not on the allowlist
+// Shared by the group and ROSCA entrypoints, which pass their settings one
+// parameter at a time
+#[allow(clippy::too_many_arguments)]
 pub fn create_group_save(
     env: &Env,
     creator: Address,
+    asset: Address,
     title: String,
     description: String,
     category: String,
@@ -61,7 +82,7 @@ pub fn create_group_save(
     }
 
     // Validate contribution_type is reasonable (0-2 for fixed/flexible/percentage)
-    if contribution_type > 2 {
+    if contribution_type > CONTRIBUTION_PERCENTAGE {
         return Err(SavingsError::InvalidGroupConfig);
     }
 
@@ -75,6 +96,12 @@ pub fn create_group_save(
         return Err(SavingsError::InvalidGroupConfig);
     }
 
+    assets::require_supported_asset(env, &asset)?;
+
+    // The group rate in force at creation applies for the life of the group
+    let interest_rate =
+        u32::try_from(rates::get_group_rate(env)).map_err(|_| SavingsError::InvalidInterestRate)?;
+
     // Get the next group ID
     let next_id_key = DataKey::NextGroupId;
     let group_id: u64 = env.storage().persistent().get(&next_id_key).unwrap_or(1u64);
@@ -86,6 +113,7 @@ pub fn create_group_save(
         title,
         description,
         category,
+        asset,
         target_amount,
         current_amount: 0,
         contribution_type,
@@ -95,6 +123,13 @@ pub fn create_group_save(
         start_time,
         end_time,
         is_completed: false,
+        interest_rate,
+        total_weight: 0,
+        last_accrual: env.ledger().timestamp(),
+        // One contribution per member over the group's life until the
+        // creator sets a shorter period
+        period_seconds: end_time - start_time,
+        max_members: 0,
     };
 
     // Store the GroupSave in persistent storage
@@ -154,6 +189,139 @@ pub fn create_group_save(
     Ok(group_id)
 }
 
+/// Switches the asset a group is denominated in.
+///
+/// Only the creator may do this, and only before any contributions have been
+/// made, so no balances ever need converting between assets.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group already holds contributions
+/// * `UnsupportedAsset` - If the asset is not on the allowlist
+pub fn set_group_asset(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    asset: Address,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+
+    let group_key = DataKey::GroupSave(group_id);
+    let mut group: GroupSave = env
+        .storage()
+        .persistent()
+        .get(&group_key)
+        .ok_or(SavingsError::PlanNotFound)?;
+
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    if group.current_amount != 0 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    assets::require_supported_asset(env, &asset)?;
+
+    group.asset = asset.clone();
+    env.storage().persistent().set(&group_key, &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events()
+        .publish((soroban_sdk::symbol_short!("grp_asset"), group_id), asset);
+
+    Ok(())
+}
+
+/// Sets the contribution period of a fixed or percentage group, in which
+/// each member contributes once. It defaults to the group's whole span.
+///
+/// Only the creator may do this, and only before any contributions have been
+/// made.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidTimestamp` - If `period_seconds` is 0
+/// * `InvalidGroupConfig` - If the group is flexible or a ROSCA, or already
+///   holds contributions
+pub fn set_group_period(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    period_seconds: u64,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+
+    let mut group = get_creator_group(env, &creator, group_id)?;
+    if period_seconds == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+    if group.contribution_type == CONTRIBUTION_FLEXIBLE
+        || group.current_amount != 0
+        || rosca::get_rosca_state(env, group_id).is_some()
+    {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    group.period_seconds = period_seconds;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_prd"), group_id),
+        period_seconds,
+    );
+
+    Ok(())
+}
+
+/// Caps the number of members a group admits; 0 removes the cap.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group already has more members than the cap
+pub fn set_group_max_members(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    max_members: u32,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+
+    let mut group = get_creator_group(env, &creator, group_id)?;
+    if max_members != 0 && max_members < group.member_count {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    group.max_members = max_members;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+    ttl::extend_group_ttl(env, group_id);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_max"), group_id),
+        max_members,
+    );
+
+    Ok(())
+}
+
+fn get_creator_group(
+    env: &Env,
+    creator: &Address,
+    group_id: u64,
+) -> Result<GroupSave, SavingsError> {
+    let group = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+    if group.creator != *creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(group)
+}
+
 /// Retrieves a group savings plan by ID.
 ///
 /// # Arguments
@@ -240,7 +408,8 @@ fn add_group_to_user_list(env: &Env, user: &Address, group_id: u64) -> Result<()
     Ok(())
 }
 
-/// Allows a user to join a public group savings plan.
+/// Allows a user to join a public group savings plan, or a private one they
+/// have been invited to.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -252,10 +421,35 @@ fn add_group_to_user_list(env: &Env, user: &Address, group_id: u64) -> Result<()
 /// `Err(SavingsError)` if:
 /// - User doesn't exist
 /// - Group doesn't exist
-/// - Group is not public
+/// - Group is private and the user has no invitation, or is public and
+///   requires the creator's approval
 /// - User is already a member
 pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+
+    let group = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+
+    // Private groups admit invited users; public ones may need approval
+    if group.is_public {
+        if invites::requires_approval(env, group_id) {
+            return Err(SavingsError::InvalidGroupConfig);
+        }
+    } else if !invites::take_invite(env, group_id, &user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    add_member(env, user, group_id)
+}
+
+/// Adds `user` to a group once the caller has checked they may join it.
+///
+/// # Errors
+/// * `UserNotFound` - If the user doesn't exist
+/// * `PlanNotFound` - If the group doesn't exist
+/// * `InvalidGroupConfig` - If the user is already a member, or the group is
+///   a ROSCA whose membership is closed to them
+/// * `GroupFull` - If the group has reached its member limit
+pub(crate) fn add_member(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
     // Ensure user exists
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
@@ -269,9 +463,10 @@ pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), Sa
         .get(&group_key)
         .ok_or(SavingsError::PlanNotFound)?;
 
-    // Validate that the group is public
-    if !group.is_public {
-        return Err(SavingsError::InvalidGroupConfig);
+    rosca::check_membership_change(env, group_id, &user)?;
+
+    if group.max_members != 0 && group.member_count >= group.max_members {
+        return Err(SavingsError::GroupFull);
     }
 
     // Check if user is already a member
@@ -351,16 +546,31 @@ pub fn join_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), Sa
 /// # Returns
 /// `Ok(())` on success
 /// `Err(SavingsError)` if:
-/// - Amount is invalid (<= 0)
+/// - Amount is invalid (<= 0) or breaks the group's contribution type
 /// - User is not a member
 /// - Group doesn't exist
+/// - User already paid into the current period of a fixed group
 pub fn contribute_to_group_save(
     env: &Env,
     user: Address,
     group_id: u64,
     amount: i128,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+    contribute_to_group_save_funded(env, user, group_id, amount, Funding::Signed)
+}
+
+/// Group contribution without the caller check; `funding` selects how the
+/// contribution is pulled from the member's wallet.
+pub(crate) fn contribute_to_group_save_funded(
+    env: &Env,
+    user: Address,
+    group_id: u64,
+    amount: i128,
+    funding: Funding,
 ) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+
     // Validate amount > 0
     if amount <= 0 {
         return Err(SavingsError::InvalidAmount);
@@ -396,6 +606,16 @@ pub fn contribute_to_group_save(
         return Err(SavingsError::NotGroupMember);
     }
 
+    // ROSCA groups take one fixed contribution per member per cycle
+    let is_rosca = rosca::record_contribution(env, &group, &user, amount)?;
+    if !is_rosca {
+        check_contribution_type(env, &group, &user, amount)?;
+    }
+
+    // Settle interest weights on the balances as they stood before this contribution
+    settle_group_weight(env, &mut group)?;
+    settle_member_weight(env, &group, &user)?;
+
     // Update user's contribution
     let contribution_key = DataKey::GroupMemberContribution(group_id, user.clone());
     let current_contribution: i128 = env
@@ -411,14 +631,19 @@ pub fn contribute_to_group_save(
     // Update group's current_amount
     group.current_amount += amount;
 
-    // Check if goal is reached
-    if group.current_amount >= group.target_amount {
+    // Check if goal is reached; a ROSCA completes when its last pot is paid
+    if !is_rosca && !group.is_completed && group.current_amount >= group.target_amount {
         group.is_completed = true;
+        book_group_interest(env, &group)?;
     }
 
     // Save updated group
     env.storage().persistent().set(&group_key, &group);
 
+    // Move the contribution into contract custody
+    ledger::adjust(env, Liability::Group, &group.asset, amount)?;
+    assets::pull_funded(env, &group.asset, &user, amount, funding);
+
     // Update the user's SavingsPlan to reflect the new balance
     let plan_key = DataKey::SavingsPlan(user.clone(), group_id);
     if let Some(mut plan) = env
@@ -469,6 +694,74 @@ pub fn contribute_to_group_save(
     Ok(())
 }
 
+/// Enforces the group's contribution type on a contribution of `amount`,
+/// recording the period a fixed or percentage member pays into.
+///
+/// A percentage group's share is fixed by the first contribution of each
+/// period, so members joining or leaving mid-period do not change it.
+///
+/// # Errors
+/// * `InvalidAmount` - If a fixed or percentage contribution is not the
+///   required amount, or a flexible one is below the minimum
+/// * `AmountExceedsLimit` - If the member already paid into the current
+///   period
+fn check_contribution_type(
+    env: &Env,
+    group: &GroupSave,
+    user: &Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    if group.contribution_type == CONTRIBUTION_FLEXIBLE {
+        if amount < group.contribution_amount {
+            return Err(SavingsError::InvalidAmount);
+        }
+        return Ok(());
+    }
+
+    let period =
+        env.ledger().timestamp().saturating_sub(group.start_time) / group.period_seconds.max(1);
+    let required = if group.contribution_type == CONTRIBUTION_FIXED {
+        group.contribution_amount
+    } else {
+        period_share(env, group, period)?
+    };
+    if amount != required {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let key = GroupDataKey::LastPeriod(group.id, user.clone());
+    let last: Option<u64> = env.storage().persistent().get(&key);
+    if last.is_some_and(|last| last >= period) {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+    env.storage().persistent().set(&key, &period);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    Ok(())
+}
+
+/// Returns a percentage group's per-member share for `period`, storing it
+/// the first time the period is paid into.
+fn period_share(env: &Env, group: &GroupSave, period: u64) -> Result<i128, SavingsError> {
+    let key = GroupDataKey::PeriodShare(group.id, period);
+    if let Some(share) = env.storage().persistent().get(&key) {
+        return Ok(share);
+    }
+    // Rounded up so the members' shares together reach the target
+    let share = math::mul_div(
+        group.target_amount,
+        1,
+        i128::from(group.member_count.max(1)),
+        Rounding::Up,
+    )?;
+    env.storage().persistent().set(&key, &share);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    Ok(share)
+}
+
 /// VIEW FUNCTION - Gets a member's contribution to a group
 ///
 /// # Arguments
@@ -559,6 +852,7 @@ fn remove_group_from_user_list(
 /// - Group is already completed
 pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
     ensure_not_paused(env)?;
+    user.require_auth();
 
     // Ensure user exists
     if !users::user_exists(env, &user) {
@@ -602,6 +896,7 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
     if !is_member {
         return Err(SavingsError::NotGroupMember);
     }
+    rosca::check_membership_change(env, group_id, &user)?;
 
     // Remove user from members list
     let mut new_members = Vec::new(env);
@@ -625,6 +920,17 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
         .get(&contribution_key)
         .unwrap_or(0i128);
 
+    // A leaving member forfeits their interest, so their weight leaves the pool
+    settle_group_weight(env, &mut group)?;
+    let member_weight = get_member_weight(env, &group, &user)?;
+    group.total_weight = group
+        .total_weight
+        .checked_sub(member_weight)
+        .ok_or(SavingsError::Underflow)?;
+    env.storage()
+        .persistent()
+        .remove(&DataKey::GroupMemberAccrual(group_id, user.clone()));
+
     // Update group's current_amount
     group.current_amount = group.current_amount.saturating_sub(user_contribution);
 
@@ -633,6 +939,9 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
 
     // Remove user's contribution entry
     env.storage().persistent().remove(&contribution_key);
+    env.storage()
+        .persistent()
+        .remove(&GroupDataKey::LastPeriod(group_id, user.clone()));
 
     // Remove group from user's list of groups
     remove_group_from_user_list(env, &user, group_id)?;
@@ -641,6 +950,10 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
     let plan_key = DataKey::SavingsPlan(user.clone(), group_id);
     env.storage().persistent().remove(&plan_key);
 
+    // Refund the user's contributions out of contract custody
+    ledger::adjust(env, Liability::Group, &group.asset, -user_contribution)?;
+    assets::pay(env, &group.asset, &user, user_contribution);
+
     // Extend TTL for group (still active for other members)
     ttl::extend_group_ttl(env, group_id);
 
@@ -652,3 +965,226 @@ pub fn break_group_save(env: &Env, user: Address, group_id: u64) -> Result<(), S
 
     Ok(())
 }
+
+/// Claims a member's share of the interest earned by a completed group.
+///
+/// Each member's share is proportional to their time-weighted contribution
+/// (contribution amount multiplied by the time it was held in the pool).
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `TooEarly` - If the group has not completed
+/// * `NotGroupMember` - If the user is not a member of the group
+/// * `PlanCompleted` - If the member has already claimed their share
+pub fn claim_group_interest(env: &Env, user: Address, group_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let group: GroupSave = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+    if !group.is_completed {
+        return Err(SavingsError::TooEarly);
+    }
+    if !get_group_members(env, group_id).contains(&user) {
+        return Err(SavingsError::NotGroupMember);
+    }
+
+    let mut accrual = get_member_accrual(env, group_id, &user);
+    if accrual.claimed {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    accrual.weight = get_member_weight(env, &group, &user)?;
+    accrual.last_accrual = group.last_accrual;
+    accrual.claimed = true;
+    let share = interest_for_weight(accrual.weight, group.interest_rate)?;
+    set_member_accrual(env, group_id, &user, &accrual);
+
+    // Interest is paid out of contract custody
+    ledger::adjust(env, Liability::Interest, &group.asset, -share)?;
+    assets::pay(env, &group.asset, &user, share);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_int"), user, group_id),
+        share,
+    );
+
+    Ok(share)
+}
+
+/// Returns a member's contributions to a completed group out of contract
+/// custody. Their share of the group's interest stays claimable.
+///
+/// # Returns
+/// The principal returned
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `TooEarly` - If the group has not completed
+/// * `NotGroupMember` - If the user is not a member of the group
+/// * `PlanCompleted` - If the member has nothing left to withdraw
+pub fn withdraw_completed_group_save(
+    env: &Env,
+    user: Address,
+    group_id: u64,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    user.require_auth();
+
+    let mut group: GroupSave = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+    if !group.is_completed {
+        return Err(SavingsError::TooEarly);
+    }
+    if !get_group_members(env, group_id).contains(&user) {
+        return Err(SavingsError::NotGroupMember);
+    }
+
+    let contribution = get_member_contribution(env, group_id, &user);
+    if contribution <= 0 {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    // Weights are frozen at completion, so settling first keeps the
+    // member's interest share intact once their contribution is zeroed
+    settle_member_weight(env, &group, &user)?;
+    env.storage().persistent().set(
+        &DataKey::GroupMemberContribution(group_id, user.clone()),
+        &0i128,
+    );
+
+    group.current_amount = group
+        .current_amount
+        .checked_sub(contribution)
+        .ok_or(SavingsError::Underflow)?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group_id), &group);
+
+    let plan_key = DataKey::SavingsPlan(user.clone(), group_id);
+    if let Some(mut plan) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, crate::storage_types::SavingsPlan>(&plan_key)
+    {
+        plan.balance = 0;
+        plan.is_withdrawn = true;
+        plan.last_withdraw = env.ledger().timestamp();
+        env.storage().persistent().set(&plan_key, &plan);
+        ttl::extend_plan_ttl(env, &plan_key);
+    }
+    ttl::extend_group_ttl(env, group_id);
+
+    ledger::adjust(env, Liability::Group, &group.asset, -contribution)?;
+    assets::pay(env, &group.asset, &user, contribution);
+
+    env.events().publish(
+        (soroban_sdk::symbol_short!("grp_wdraw"), user, group_id),
+        contribution,
+    );
+
+    Ok(contribution)
+}
+
+/// VIEW FUNCTION - Gets the interest the whole group pool has earned so far
+pub fn get_group_accrued_interest(env: &Env, group_id: u64) -> Result<i128, SavingsError> {
+    let mut group: GroupSave = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+    settle_group_weight(env, &mut group)?;
+    interest_for_weight(group.total_weight, group.interest_rate)
+}
+
+/// VIEW FUNCTION - Gets a member's share of the group's interest so far
+pub fn get_member_interest_share(
+    env: &Env,
+    group_id: u64,
+    user: &Address,
+) -> Result<i128, SavingsError> {
+    let group: GroupSave = get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)?;
+    let weight = get_member_weight(env, &group, user)?;
+    interest_for_weight(weight, group.interest_rate)
+}
+
+/// Interest earned by `weight` contribution-seconds at `rate` basis points a year.
+fn interest_for_weight(weight: i128, rate: u32) -> Result<i128, SavingsError> {
+    let denominator = math::BPS_DENOMINATOR
+        .checked_mul(math::SECONDS_PER_YEAR)
+        .ok_or(SavingsError::Overflow)?;
+    math::mul_div(weight, rate as i128, denominator, Rounding::Down)
+}
+
+/// Books the interest every member of a group that has just completed is
+/// owed, which `claim_group_interest` pays out share by share.
+fn book_group_interest(env: &Env, group: &GroupSave) -> Result<(), SavingsError> {
+    let mut owed = 0i128;
+    for member in get_group_members(env, group.id).iter() {
+        let weight = get_member_weight(env, group, &member)?;
+        owed = owed
+            .checked_add(interest_for_weight(weight, group.interest_rate)?)
+            .ok_or(SavingsError::Overflow)?;
+    }
+    ledger::adjust(env, Liability::Interest, &group.asset, owed)
+}
+
+/// The time up to which weights accrue: now, or the moment the group completed.
+fn accrual_cutoff(env: &Env, group: &GroupSave) -> u64 {
+    if group.is_completed {
+        group.last_accrual
+    } else {
+        env.ledger().timestamp()
+    }
+}
+
+/// Adds the pool's contribution-seconds since the last update to `total_weight`.
+fn settle_group_weight(env: &Env, group: &mut GroupSave) -> Result<(), SavingsError> {
+    let now = accrual_cutoff(env, group);
+    let elapsed = now.saturating_sub(group.last_accrual);
+    let added = group
+        .current_amount
+        .checked_mul(elapsed as i128)
+        .ok_or(SavingsError::Overflow)?;
+    group.total_weight = group
+        .total_weight
+        .checked_add(added)
+        .ok_or(SavingsError::Overflow)?;
+    group.last_accrual = now;
+    Ok(())
+}
+
+fn get_member_accrual(env: &Env, group_id: u64, user: &Address) -> GroupMemberAccrual {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GroupMemberAccrual(group_id, user.clone()))
+        .unwrap_or(GroupMemberAccrual {
+            weight: 0,
+            last_accrual: 0,
+            claimed: false,
+        })
+}
+
+fn set_member_accrual(env: &Env, group_id: u64, user: &Address, accrual: &GroupMemberAccrual) {
+    env.storage().persistent().set(
+        &DataKey::GroupMemberAccrual(group_id, user.clone()),
+        accrual,
+    );
+}
+
+/// A member's contribution-seconds up to the group's accrual cutoff.
+fn get_member_weight(env: &Env, group: &GroupSave, user: &Address) -> Result<i128, SavingsError> {
+    let accrual = get_member_accrual(env, group.id, user);
+    let contribution = get_member_contribution(env, group.id, user);
+    let elapsed = accrual_cutoff(env, group).saturating_sub(accrual.last_accrual);
+    let added = contribution
+        .checked_mul(elapsed as i128)
+        .ok_or(SavingsError::Overflow)?;
+    accrual
+        .weight
+        .checked_add(added)
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Brings a member's weight up to the group's accrual cutoff.
+fn settle_member_weight(env: &Env, group: &GroupSave, user: &Address) -> Result<(), SavingsError> {
+    let mut accrual = get_member_accrual(env, group.id, user);
+    accrual.weight = get_member_weight(env, group, user)?;
+    accrual.last_accrual = accrual_cutoff(env, group);
+    set_member_accrual(env, group.id, user, &accrual);
+    Ok(())
+}
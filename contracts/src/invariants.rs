@@ -26,6 +26,14 @@ pub fn assert_sufficient_balance(balance: i128, amount: i128) -> Result<(), Savi
     Ok(())
 }
 
+/// Validates that the contract holds at least what it owes in an asset.
+pub fn assert_solvent(holdings: i128, liabilities: i128) -> Result<(), SavingsError> {
+    if holdings < liabilities {
+        return Err(SavingsError::InvariantViolation);
+    }
+    Ok(())
+}
+
 // Checks if the contract is currently paused.
 // pub fn assert_not_paused(_env: &Env) -> Result<(), SavingsError> {
 //     // This assumes you have a State or Config object in storage
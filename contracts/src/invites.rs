@@ -0,0 +1,288 @@
+use crate::errors::SavingsError;
+use crate::group;
+use crate::storage_types::{GroupDataKey, GroupSave};
+use crate::ttl;
+use crate::users;
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{symbol_short, Address, Bytes, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Invites `invitee` to a group; they join with `join_group_save`.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the invitee is already a member or invited
+pub fn invite_to_group(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    invitee: Address,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+    require_creator(env, &creator, group_id)?;
+
+    let mut invites = get_group_invites(env, group_id);
+    if invites.contains(&invitee) || is_member(env, group_id, &invitee) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    invites.push_back(invitee.clone());
+    save_list(env, &GroupDataKey::Invites(group_id), &invites);
+
+    env.events()
+        .publish((symbol_short!("grp_inv"), group_id), invitee);
+    Ok(())
+}
+
+/// Withdraws an outstanding invitation.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the address has no outstanding invitation
+pub fn revoke_group_invite(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    invitee: Address,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+    require_creator(env, &creator, group_id)?;
+
+    if !take_invite(env, group_id, &invitee) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    env.events()
+        .publish((symbol_short!("grp_uninv"), group_id), invitee);
+    Ok(())
+}
+
+/// Registers a single-use invite code for one invitee. `code_hash` is
+/// `sha256(code || invitee.to_xdr())`, so the code, shared off-chain, only
+/// admits the address it was issued to and cannot be front-run by anyone
+/// who sees it in a pending transaction.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the code is already registered
+pub fn add_group_invite_code(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    code_hash: BytesN<32>,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+    require_creator(env, &creator, group_id)?;
+
+    let mut codes = get_group_invite_codes(env, group_id);
+    if codes.contains(&code_hash) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    codes.push_back(code_hash.clone());
+    save_list(env, &GroupDataKey::InviteCodes(group_id), &codes);
+
+    env.events()
+        .publish((symbol_short!("grp_code"), group_id), code_hash);
+    Ok(())
+}
+
+/// Joins a public or private group by redeeming an invite code issued to
+/// `user`, which is used up.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the code was not issued to `user` or does not match
+///   an outstanding invite code
+/// * `InvalidGroupConfig` - If the user is already a member
+pub fn join_group_with_code(
+    env: &Env,
+    user: Address,
+    group_id: u64,
+    code: Bytes,
+) -> Result<(), SavingsError> {
+    user.require_auth();
+    load_group(env, group_id)?;
+
+    let code_hash = invite_code_hash(env, code, &user);
+    let mut codes = get_group_invite_codes(env, group_id);
+    let index = codes
+        .first_index_of(&code_hash)
+        .ok_or(SavingsError::Unauthorized)?;
+    codes.remove(index);
+    save_list(env, &GroupDataKey::InviteCodes(group_id), &codes);
+
+    env.events()
+        .publish((symbol_short!("grp_used"), group_id), code_hash);
+    group::add_member(env, user, group_id)
+}
+
+/// Hashes an invite code together with the address it is issued to.
+pub(crate) fn invite_code_hash(env: &Env, code: Bytes, invitee: &Address) -> BytesN<32> {
+    let mut preimage = code;
+    preimage.append(&invitee.clone().to_xdr(env));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Sets whether joining a public group needs the creator's approval.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group is private, so joins are by
+///   invitation already
+pub fn set_group_join_approval(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    required: bool,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+    let group = require_creator(env, &creator, group_id)?;
+    if !group.is_public {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let key = GroupDataKey::RequiresApproval(group_id);
+    if required {
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    env.events()
+        .publish((symbol_short!("grp_appr"), group_id), required);
+    Ok(())
+}
+
+/// Asks to join a public group that requires the creator's approval.
+///
+/// # Errors
+/// * `UserNotFound` - If the user doesn't exist
+/// * `PlanNotFound` - If the group does not exist
+/// * `InvalidGroupConfig` - If the group does not take join requests, or the
+///   user is already a member or has a pending request
+pub fn request_to_join_group(env: &Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
+    user.require_auth();
+    if !users::user_exists(env, &user) {
+        return Err(SavingsError::UserNotFound);
+    }
+    let group = load_group(env, group_id)?;
+    if !group.is_public || !requires_approval(env, group_id) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let mut requests = get_group_join_requests(env, group_id);
+    if requests.contains(&user) || is_member(env, group_id, &user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    requests.push_back(user.clone());
+    save_list(env, &GroupDataKey::JoinRequests(group_id), &requests);
+
+    env.events()
+        .publish((symbol_short!("grp_req"), user), group_id);
+    Ok(())
+}
+
+/// Accepts or rejects a pending join request; an accepted user becomes a
+/// member.
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the user has no pending request
+pub fn respond_to_join_request(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+    user: Address,
+    accept: bool,
+) -> Result<(), SavingsError> {
+    creator.require_auth();
+    require_creator(env, &creator, group_id)?;
+
+    let mut requests = get_group_join_requests(env, group_id);
+    let index = requests
+        .first_index_of(&user)
+        .ok_or(SavingsError::InvalidGroupConfig)?;
+    requests.remove(index);
+    save_list(env, &GroupDataKey::JoinRequests(group_id), &requests);
+
+    if accept {
+        group::add_member(env, user, group_id)
+    } else {
+        env.events()
+            .publish((symbol_short!("grp_rej"), user), group_id);
+        Ok(())
+    }
+}
+
+/// Returns the addresses with an outstanding invitation to a group.
+pub fn get_group_invites(env: &Env, group_id: u64) -> Vec<Address> {
+    get_list(env, &GroupDataKey::Invites(group_id))
+}
+
+/// Returns the hashes of a group's unredeemed invite codes.
+pub fn get_group_invite_codes(env: &Env, group_id: u64) -> Vec<BytesN<32>> {
+    get_list(env, &GroupDataKey::InviteCodes(group_id))
+}
+
+/// Returns the users waiting for the creator to approve their join request.
+pub fn get_group_join_requests(env: &Env, group_id: u64) -> Vec<Address> {
+    get_list(env, &GroupDataKey::JoinRequests(group_id))
+}
+
+/// Returns whether joining a public group needs the creator's approval.
+pub fn requires_approval(env: &Env, group_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&GroupDataKey::RequiresApproval(group_id))
+}
+
+/// Uses up `user`'s invitation to a group, returning whether they had one.
+pub(crate) fn take_invite(env: &Env, group_id: u64, user: &Address) -> bool {
+    let mut invites = get_group_invites(env, group_id);
+    let Some(index) = invites.first_index_of(user) else {
+        return false;
+    };
+    invites.remove(index);
+    save_list(env, &GroupDataKey::Invites(group_id), &invites);
+    true
+}
+
+fn load_group(env: &Env, group_id: u64) -> Result<GroupSave, SavingsError> {
+    group::get_group_save(env, group_id).ok_or(SavingsError::PlanNotFound)
+}
+
+fn require_creator(env: &Env, creator: &Address, group_id: u64) -> Result<GroupSave, SavingsError> {
+    let group = load_group(env, group_id)?;
+    if group.creator != *creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    Ok(group)
+}
+
+fn is_member(env: &Env, group_id: u64, user: &Address) -> bool {
+    group::get_group_members(env, group_id).contains(user)
+}
+
+fn get_list<T>(env: &Env, key: &GroupDataKey) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+fn save_list<T>(env: &Env, key: &GroupDataKey, list: &Vec<T>) {
+    if list.is_empty() {
+        env.storage().persistent().remove(key);
+        return;
+    }
+    env.storage().persistent().set(key, list);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
@@ -0,0 +1,184 @@
+use crate::assets;
+use crate::calculate_fee;
+use crate::errors::SavingsError;
+use crate::fees;
+use crate::storage_types::{AutoSaveDataKey, KeeperEarning, KeeperTip, KeeperTipConfig};
+use crate::ttl;
+use soroban_sdk::{symbol_short, Address, Env, Vec};
+
+/// Maximum keeper tip in basis points (100%)
+const MAX_TIP_BPS: u32 = 10_000;
+
+/// Returns the keeper tip settings, disabled until an admin configures them.
+pub fn get_tip_config(env: &Env) -> KeeperTipConfig {
+    env.storage()
+        .instance()
+        .get(&AutoSaveDataKey::KeeperTip)
+        .unwrap_or(KeeperTipConfig {
+            tip: KeeperTip::Disabled,
+            max_per_batch: 0,
+        })
+}
+
+/// Stores the keeper tip settings.
+///
+/// # Errors
+/// * `SavingsError::InvalidFeeBps` - If a bps tip exceeds 10000
+/// * `SavingsError::InvalidAmount` - If a flat tip is negative, or
+///   `max_per_batch` is negative, or 0 while tips are enabled
+pub fn set_tip_config(env: &Env, config: &KeeperTipConfig) -> Result<(), SavingsError> {
+    match config.tip {
+        KeeperTip::Bps(bps) if bps > MAX_TIP_BPS => return Err(SavingsError::InvalidFeeBps),
+        KeeperTip::Flat(amount) if amount < 0 => return Err(SavingsError::InvalidAmount),
+        _ => {}
+    }
+    // A zero cap would silently pay enabled tips nothing
+    if config.max_per_batch < 0 || (config.max_per_batch == 0 && config.tip != KeeperTip::Disabled)
+    {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    env.storage()
+        .instance()
+        .set(&AutoSaveDataKey::KeeperTip, config);
+    Ok(())
+}
+
+/// Adds `keeper` to or removes it from the allowlist of keepers paid tips.
+pub fn set_registered(env: &Env, keeper: &Address, registered: bool) {
+    let key = AutoSaveDataKey::Keeper(keeper.clone());
+    if registered {
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn is_registered(env: &Env, keeper: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&AutoSaveDataKey::Keeper(keeper.clone()))
+}
+
+/// Returns the tips `keeper` has been paid, per asset.
+pub fn get_earnings(env: &Env, keeper: &Address) -> Vec<KeeperEarning> {
+    env.storage()
+        .persistent()
+        .get(&AutoSaveDataKey::KeeperEarnings(keeper.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Tips owed to a keeper over one batch, accumulated per asset and capped
+/// by `max_per_batch`.
+pub(crate) struct TipBatch {
+    keeper: Option<Address>,
+    config: KeeperTipConfig,
+    owed: Vec<KeeperEarning>,
+}
+
+impl TipBatch {
+    /// Starts a batch for `keeper`, which earns nothing unless it is
+    /// registered and tips are enabled.
+    pub(crate) fn new(env: &Env, keeper: Option<Address>) -> Self {
+        let config = get_tip_config(env);
+        let keeper = match config.tip {
+            KeeperTip::Disabled => None,
+            _ => keeper.filter(|k| is_registered(env, k)),
+        };
+        TipBatch {
+            keeper,
+            config,
+            owed: Vec::new(env),
+        }
+    }
+
+    /// Records the tip for one execution that deposited `amount` of `asset`.
+    pub(crate) fn add(&mut self, asset: &Address, amount: i128) -> Result<(), SavingsError> {
+        if self.keeper.is_none() {
+            return Ok(());
+        }
+        let tip = match self.config.tip {
+            KeeperTip::Disabled => 0,
+            KeeperTip::Bps(bps) => calculate_fee(amount, bps)?,
+            KeeperTip::Flat(flat) => flat,
+        };
+
+        let index = position(&self.owed, asset);
+        let owed = index.map_or(0, |i| self.owed.get(i).unwrap().amount);
+        let entry = KeeperEarning {
+            asset: asset.clone(),
+            amount: owed
+                .checked_add(tip)
+                .ok_or(SavingsError::Overflow)?
+                .min(self.config.max_per_batch),
+        };
+        match index {
+            Some(i) => self.owed.set(i, entry),
+            None => self.owed.push_back(entry),
+        }
+        Ok(())
+    }
+
+    /// Pays the owed tips out of accrued protocol fees, as far as they
+    /// cover them, and adds them to the keeper's earnings.
+    pub(crate) fn pay(self, env: &Env) -> Result<(), SavingsError> {
+        let Some(keeper) = self.keeper else {
+            return Ok(());
+        };
+
+        let mut earnings = get_earnings(env, &keeper);
+        let mut paid_any = false;
+        for owed in self.owed.iter() {
+            let paid = fees::spend_fees(env, &owed.asset, owed.amount)?;
+            if paid <= 0 {
+                continue;
+            }
+            assets::pay(env, &owed.asset, &keeper, paid);
+            credit(&mut earnings, &owed.asset, paid)?;
+            paid_any = true;
+
+            env.events().publish(
+                (symbol_short!("kpr_tip"), keeper.clone(), owed.asset.clone()),
+                paid,
+            );
+        }
+
+        if paid_any {
+            let key = AutoSaveDataKey::KeeperEarnings(keeper);
+            env.storage().persistent().set(&key, &earnings);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+        }
+        Ok(())
+    }
+}
+
+fn credit(
+    earnings: &mut Vec<KeeperEarning>,
+    asset: &Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    match position(earnings, asset) {
+        Some(i) => {
+            let mut entry = earnings.get(i).unwrap();
+            entry.amount = entry
+                .amount
+                .checked_add(amount)
+                .ok_or(SavingsError::Overflow)?;
+            earnings.set(i, entry);
+        }
+        None => earnings.push_back(KeeperEarning {
+            asset: asset.clone(),
+            amount,
+        }),
+    }
+    Ok(())
+}
+
+fn position(entries: &Vec<KeeperEarning>, asset: &Address) -> Option<u32> {
+    (0..entries.len()).find(|&i| entries.get(i).unwrap().asset == *asset)
+}
@@ -0,0 +1,322 @@
+use crate::errors::SavingsError;
+use crate::invariants;
+use crate::storage_types::{DataKey, ProtocolFeeBalance, SolvencyReport, StrategyConfig};
+use crate::ttl;
+use soroban_sdk::{token, Address, Env, Vec};
+
+/// Categories of user funds the contract owes, tracked per asset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Liability {
+    Flexi,
+    Locked,
+    Goal,
+    Group,
+    /// Interest settled onto user positions but not yet paid or compounded
+    Interest,
+}
+
+fn key(liability: Liability, asset: &Address) -> DataKey {
+    let asset = asset.clone();
+    match liability {
+        Liability::Flexi => DataKey::TotalFlexi(asset),
+        Liability::Locked => DataKey::TotalLocked(asset),
+        Liability::Goal => DataKey::TotalGoal(asset),
+        Liability::Group => DataKey::TotalGroup(asset),
+        Liability::Interest => DataKey::TotalInterest(asset),
+    }
+}
+
+/// Adds `delta` (which may be negative) to a protocol-wide per-asset total.
+///
+/// # Errors
+/// * `SavingsError::Overflow` - If the total overflows
+/// * `SavingsError::Underflow` - If the total would drop below zero
+pub(crate) fn adjust(
+    env: &Env,
+    liability: Liability,
+    asset: &Address,
+    delta: i128,
+) -> Result<(), SavingsError> {
+    if delta == 0 {
+        return Ok(());
+    }
+
+    let key = key(liability, asset);
+    let current = get(env, liability, asset);
+    let updated = current.checked_add(delta).ok_or(SavingsError::Overflow)?;
+    if updated < 0 {
+        return Err(SavingsError::Underflow);
+    }
+
+    env.storage().persistent().set(&key, &updated);
+    ttl::extend_config_ttl(env, &key);
+    track_asset(env, asset);
+    Ok(())
+}
+
+/// Reads a protocol-wide per-asset total, defaulting to zero.
+pub(crate) fn get(env: &Env, liability: Liability, asset: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&key(liability, asset))
+        .unwrap_or(0)
+}
+
+/// User principal in `asset` held across Flexi balances and open Lock saves.
+pub fn get_flexi_lock_liquidity(env: &Env, asset: &Address) -> Result<i128, SavingsError> {
+    get(env, Liability::Flexi, asset)
+        .checked_add(get(env, Liability::Locked, asset))
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Returns every asset the ledger has ever recorded a balance in.
+pub fn get_ledger_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LedgerAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+fn track_asset(env: &Env, asset: &Address) {
+    let mut assets = get_ledger_assets(env);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::LedgerAssets, &assets);
+    }
+    ttl::extend_config_ttl(env, &DataKey::LedgerAssets);
+}
+
+/// Compares what the contract owes in `asset` against what it holds.
+///
+/// Holdings are the contract's own token balance plus the principal
+/// allocated to the asset's yield strategy. Interest counts once it is
+/// settled onto a position: Flexi, Goal and Lock yield as it is settled, and
+/// group interest when the group completes. A surplus is expected (reserves
+/// fund interest before it is settled), so only a shortfall counts as a
+/// violation.
+pub fn get_solvency_report(env: &Env, asset: Address) -> Result<SolvencyReport, SavingsError> {
+    let fees = env
+        .storage()
+        .persistent()
+        .get::<DataKey, ProtocolFeeBalance>(&DataKey::AssetFees(asset.clone()))
+        .map(|ledger| ledger.accrued)
+        .unwrap_or(0);
+
+    let flexi = get(env, Liability::Flexi, &asset);
+    let locked = get(env, Liability::Locked, &asset);
+    let goal = get(env, Liability::Goal, &asset);
+    let group = get(env, Liability::Group, &asset);
+    let interest = get(env, Liability::Interest, &asset);
+
+    let liabilities = [locked, goal, group, fees, interest]
+        .iter()
+        .try_fold(flexi, |total, value| total.checked_add(*value))
+        .ok_or(SavingsError::Overflow)?;
+
+    let allocated = env
+        .storage()
+        .persistent()
+        .get::<DataKey, StrategyConfig>(&DataKey::Strategy(asset.clone()))
+        .map(|cfg| cfg.allocated)
+        .unwrap_or(0);
+    let holdings = token::Client::new(env, &asset)
+        .balance(&env.current_contract_address())
+        .checked_add(allocated)
+        .ok_or(SavingsError::Overflow)?;
+
+    Ok(SolvencyReport {
+        asset,
+        flexi,
+        locked,
+        goal,
+        group,
+        fees,
+        interest,
+        liabilities,
+        holdings,
+    })
+}
+
+/// Checks every asset the ledger tracks for an accounting shortfall.
+///
+/// # Errors
+/// * `SavingsError::InvariantViolation` - If the liabilities recorded in any
+///   asset exceed what the contract holds in it
+pub fn check_solvency(env: &Env) -> Result<Vec<SolvencyReport>, SavingsError> {
+    let mut reports = Vec::new(env);
+    for asset in get_ledger_assets(env).iter() {
+        let report = get_solvency_report(env, asset)?;
+        invariants::assert_solvent(report.holdings, report.liabilities)?;
+        reports.push_back(report);
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage_types::DataKey;
+    use crate::{NesteraContract, NesteraContractClient, SavingsError};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        token::StellarAssetClient,
+        Address, BytesN, Env, String, Symbol,
+    };
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    fn setup() -> (Env, NesteraContractClient<'static>, Address, Address) {
+        let env = Env::default();
+        let client = NesteraContractClient::new(&env, &env.register(NesteraContract, ()));
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+        client.set_lock_rate(&admin, &0, &500);
+        client.set_protocol_fee_bps(&100);
+
+        (env, client, admin, token)
+    }
+
+    fn new_user(env: &Env, client: &NesteraContractClient, token: &Address) -> Address {
+        let user = Address::generate(env);
+        client.initialize_user(&user);
+        StellarAssetClient::new(env, token).mint(&user, &100_000);
+        user
+    }
+
+    #[test]
+    fn test_ledger_tracks_every_plan_type() {
+        let (env, client, admin, token) = setup();
+        let user = new_user(&env, &client, &token);
+
+        client.deposit_flexi(&user, &10_000);
+        client.create_lock_save(&user, &5_000, &YEAR);
+        client.create_goal_save(&user, &Symbol::new(&env, "car"), &50_000, &2_000, &None);
+        let group_id = client.create_group_save(
+            &user,
+            &String::from_str(&env, "Group"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "savings"),
+            &10_000,
+            &0,
+            &3_000,
+            &true,
+            &1,
+            &1000,
+        );
+        client.contribute_to_group_save(&user, &group_id, &3_000);
+
+        let report = client.get_solvency_report(&token);
+        assert_eq!(report.flexi, 9_900);
+        assert_eq!(report.locked, 5_000);
+        assert_eq!(report.goal, 1_980);
+        assert_eq!(report.group, 3_000);
+        assert_eq!(report.fees, 120);
+        assert_eq!(report.interest, 0);
+        assert_eq!(report.liabilities, 20_000);
+        assert_eq!(report.holdings, 20_000);
+
+        // Reading accrued Flexi interest does not settle it
+        client.set_flexi_rate(&admin, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+        assert_eq!(client.get_flexi_accrued_interest(&user), 990);
+        assert_eq!(client.get_flexi_balance(&user), 9_900);
+        assert_eq!(client.get_solvency_report(&token).interest, 0);
+
+        StellarAssetClient::new(&env, &token).mint(&client.address, &10_000);
+        client.claim_flexi_interest(&user);
+        client.withdraw_lock_save(&user, &1);
+        client.break_group_save(&user, &group_id);
+
+        let reports = client.check_solvency();
+        assert_eq!(reports.len(), 1);
+        let report = reports.get(0).unwrap();
+        assert_eq!(report.flexi, 10_890);
+        assert_eq!(report.locked, 0);
+        assert_eq!(report.group, 0);
+        assert_eq!(report.interest, 0);
+        assert!(report.holdings >= report.liabilities);
+    }
+
+    #[test]
+    fn test_lock_and_group_interest_is_booked_until_paid() {
+        let (env, client, admin, token) = setup();
+        let user = new_user(&env, &client, &token);
+        StellarAssetClient::new(&env, &token).mint(&client.address, &10_000);
+
+        // Lock yield is booked as it is settled, then paid out at maturity
+        let lock_id = client.create_lock_save(&user, &10_000, &YEAR);
+        env.ledger().with_mut(|li| li.timestamp += YEAR / 2);
+        client.top_up_lock_save(&user, &lock_id, &1_000);
+        assert_eq!(client.get_solvency_report(&token).interest, 250);
+        env.ledger().with_mut(|li| li.timestamp += YEAR / 2);
+        client.withdraw_lock_save(&user, &lock_id);
+        assert_eq!(client.get_solvency_report(&token).interest, 0);
+
+        // Group interest is booked when the group completes
+        client.set_group_rate(&admin, &1_000);
+        let group_id = client.create_group_save(
+            &user,
+            &String::from_str(&env, "Group"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "savings"),
+            &2_000,
+            &1,
+            &100,
+            &true,
+            &1,
+            &(10 * YEAR),
+        );
+        client.contribute_to_group_save(&user, &group_id, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+        client.contribute_to_group_save(&user, &group_id, &1_000);
+        assert_eq!(client.get_solvency_report(&token).interest, 100);
+
+        client.claim_group_interest(&user, &group_id);
+        let report = client.get_solvency_report(&token);
+        assert_eq!(report.interest, 0);
+        assert!(report.holdings >= report.liabilities);
+    }
+
+    #[test]
+    fn test_unfunded_interest_is_flagged() {
+        let (env, client, admin, token) = setup();
+        let user = new_user(&env, &client, &token);
+        client.deposit_flexi(&user, &10_000);
+        assert!(client.try_check_solvency().is_ok());
+
+        // Interest is owed but no reserve has been funded to pay it
+        client.set_flexi_rate(&admin, &1_000);
+        env.ledger().with_mut(|li| li.timestamp += YEAR);
+        client.claim_flexi_interest(&user);
+
+        assert_eq!(
+            client.try_check_solvency(),
+            Err(Ok(SavingsError::InvariantViolation))
+        );
+    }
+
+    #[test]
+    fn test_accounting_drift_is_flagged() {
+        let (env, client, _admin, token) = setup();
+        let user = new_user(&env, &client, &token);
+        client.deposit_flexi(&user, &10_000);
+
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::TotalFlexi(token.clone()), &10_000i128);
+        });
+
+        assert_eq!(
+            client.try_check_solvency(),
+            Err(Ok(SavingsError::InvariantViolation))
+        );
+    }
+}
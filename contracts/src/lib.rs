@@ -5,18 +5,26 @@ use soroban_sdk::{
     Env, String, Symbol, Vec,
 };
 
+mod assets;
 mod autosave;
 mod config;
 mod errors;
+mod fees;
 mod flexi;
 mod goal;
 mod governance;
 mod group;
 mod invariants;
+mod invites;
+mod keeper;
+mod ledger;
 mod lock;
+mod math;
 
 pub mod rewards;
+mod rosca;
 mod storage_types;
+mod strategy;
 mod ttl;
 mod upgrade;
 mod users;
@@ -30,9 +38,13 @@ mod views;
 pub use crate::config::Config;
 pub use crate::errors::SavingsError;
 pub use crate::storage_types::{
-    AutoSave, DataKey, GoalSave, GoalSaveView, GroupSave, GroupSaveView, LockSave, LockSaveView,
-    MintPayload, PlanType, SavingsPlan, User,
+    AssetHolding, AutoSave, AutoSaveDataKey, AutoSaveDestination, AutoSaveStatus, DataKey,
+    FeeCredit, FeeDataKey, GoalSave, GoalSaveView, GroupDataKey, GroupSave, GroupSaveView,
+    KeeperEarning, KeeperTip, KeeperTipConfig, LockSave, LockSaveView, MintPayload,
+    MissedIntervalPolicy, PlanType, ProtocolFeeBalance, RoscaState, SavingsPlan, SolvencyReport,
+    StrategyAllocation, User,
 };
+pub use crate::strategy::{YieldStrategy, YieldStrategyClient};
 
 /// Custom error codes for the contract administration
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -71,12 +83,8 @@ pub(crate) fn calculate_fee(amount: i128, fee_bps: u32) -> Result<i128, SavingsE
     // Task 1: Use invariant check for valid fee range
     invariants::assert_valid_fee(fee_bps)?;
 
-    // Task 4: Proper overflow protection
-    let total = amount
-        .checked_mul(fee_bps as i128)
-        .ok_or(SavingsError::Overflow)?;
-
-    Ok(total / 10_000)
+    // Fees round down so users are never charged more than the quoted rate
+    math::apply_bps(amount, fee_bps as i128, math::Rounding::Down)
 }
 
 #[cfg(test)]
@@ -269,16 +277,75 @@ impl NesteraContract {
 
     pub fn deposit_flexi(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
         ensure_not_paused(&env)?;
-        flexi::flexi_deposit(env, user, amount)
+        let asset = config::get_settlement_asset(&env)?;
+        flexi::flexi_deposit(env, user, asset, amount)
     }
 
     pub fn withdraw_flexi(env: Env, user: Address, amount: i128) -> Result<(), SavingsError> {
         ensure_not_paused(&env)?;
-        flexi::flexi_withdraw(env, user, amount)
+        let asset = config::get_settlement_asset(&env)?;
+        flexi::flexi_withdraw(env, user, asset, amount)
     }
 
     pub fn get_flexi_balance(env: Env, user: Address) -> i128 {
-        flexi::get_flexi_balance(&env, user).unwrap_or(0)
+        match config::get_settlement_asset(&env) {
+            Ok(asset) => flexi::get_flexi_balance(&env, user, asset).unwrap_or(0),
+            Err(_) => 0,
+        }
+    }
+
+    pub fn deposit_flexi_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        flexi::flexi_deposit(env, user, asset, amount)
+    }
+
+    pub fn withdraw_flexi_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        flexi::flexi_withdraw(env, user, asset, amount)
+    }
+
+    pub fn get_flexi_asset_balance(env: Env, user: Address, asset: Address) -> i128 {
+        flexi::get_flexi_balance(&env, user, asset).unwrap_or(0)
+    }
+
+    /// Returns the Flexi interest earned in the settlement asset but not yet claimed
+    pub fn get_flexi_accrued_interest(env: Env, user: Address) -> Result<i128, SavingsError> {
+        let asset = config::get_settlement_asset(&env)?;
+        Ok(flexi::pending_flexi_interest(&env, &user, &asset)?.pending)
+    }
+
+    /// Returns the Flexi interest earned in `asset` but not yet claimed
+    pub fn get_flexi_accrued_interest_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+    ) -> Result<i128, SavingsError> {
+        Ok(flexi::pending_flexi_interest(&env, &user, &asset)?.pending)
+    }
+
+    /// Compounds pending Flexi interest in the settlement asset into the balance
+    pub fn claim_flexi_interest(env: Env, user: Address) -> Result<i128, SavingsError> {
+        let asset = config::get_settlement_asset(&env)?;
+        flexi::claim_flexi_interest(&env, user, asset)
+    }
+
+    /// Compounds pending Flexi interest in `asset` into the balance
+    pub fn claim_flexi_interest_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+    ) -> Result<i128, SavingsError> {
+        flexi::claim_flexi_interest(&env, user, asset)
     }
 
     // --- Lock Save Logic ---
@@ -286,7 +353,22 @@ impl NesteraContract {
     pub fn create_lock_save(env: Env, user: Address, amount: i128, duration: u64) -> u64 {
         ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
         user.require_auth();
-        lock::create_lock_save(&env, user, amount, duration)
+        let asset =
+            config::get_settlement_asset(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        lock::create_lock_save(&env, user, asset, amount, duration)
+            .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    pub fn create_lock_save_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+        duration: u64,
+    ) -> u64 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        user.require_auth();
+        lock::create_lock_save(&env, user, asset, amount, duration)
             .unwrap_or_else(|e| panic_with_error!(&env, e))
     }
 
@@ -296,10 +378,52 @@ impl NesteraContract {
         lock::withdraw_lock_save(&env, user, lock_id).unwrap_or_else(|e| panic_with_error!(&env, e))
     }
 
+    /// Breaks a lock before maturity for principal minus the time-scaled
+    /// lock break penalty; any yield is forfeited
+    pub fn break_lock_save(env: Env, user: Address, lock_id: u64) -> i128 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        user.require_auth();
+        lock::break_lock_save(&env, user, lock_id).unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    /// Adds principal to an open lock, keeping its maturity; returns the new
+    /// principal
+    pub fn top_up_lock_save(env: Env, user: Address, lock_id: u64, amount: i128) -> i128 {
+        user.require_auth();
+        lock::top_up_lock_save(&env, user, lock_id, amount)
+            .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    /// Pushes an open lock's maturity later and re-prices it for the longer
+    /// term; returns the new maturity time
+    pub fn extend_lock_save(env: Env, user: Address, lock_id: u64, extra_duration: u64) -> u64 {
+        user.require_auth();
+        lock::extend_lock_save(&env, user, lock_id, extra_duration)
+            .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    /// Opts a lock in or out of automatic rollover at maturity
+    pub fn set_lock_rollover(env: Env, user: Address, lock_id: u64, enabled: bool) {
+        user.require_auth();
+        lock::set_lock_rollover(&env, user, lock_id, enabled)
+            .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    /// Re-locks a matured, rollover-enabled lock for another term at the
+    /// current rate; callable by any keeper. Returns the new lock ID
+    pub fn rollover_lock_save(env: Env, lock_id: u64) -> u64 {
+        lock::rollover_lock_save(&env, lock_id).unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
     pub fn check_matured_lock(env: Env, lock_id: u64) -> bool {
         lock::check_matured_lock(&env, lock_id)
     }
 
+    pub fn get_lock_save_detail(env: Env, lock_id: u64) -> LockSave {
+        lock::get_lock_save(&env, lock_id)
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsError::PlanNotFound))
+    }
+
     pub fn get_user_lock_saves(env: Env, user: Address) -> Vec<u64> {
         lock::get_user_lock_saves(&env, &user)
     }
@@ -312,10 +436,43 @@ impl NesteraContract {
         goal_name: Symbol,
         target_amount: i128,
         initial_deposit: i128,
+        deadline: Option<u64>,
     ) -> u64 {
         ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
-        goal::create_goal_save(&env, user, goal_name, target_amount, initial_deposit)
-            .unwrap_or_else(|e| panic_with_error!(&env, e))
+        let asset =
+            config::get_settlement_asset(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        goal::create_goal_save(
+            &env,
+            user,
+            asset,
+            goal_name,
+            target_amount,
+            initial_deposit,
+            deadline,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
+    pub fn create_goal_save_asset(
+        env: Env,
+        user: Address,
+        asset: Address,
+        goal_name: Symbol,
+        target_amount: i128,
+        initial_deposit: i128,
+        deadline: Option<u64>,
+    ) -> u64 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        goal::create_goal_save(
+            &env,
+            user,
+            asset,
+            goal_name,
+            target_amount,
+            initial_deposit,
+            deadline,
+        )
+        .unwrap_or_else(|e| panic_with_error!(&env, e))
     }
 
     pub fn deposit_to_goal_save(env: Env, user: Address, goal_id: u64, amount: i128) {
@@ -335,17 +492,41 @@ impl NesteraContract {
         goal::break_goal_save(&env, user, goal_id).unwrap_or_else(|e| panic_with_error!(&env, e))
     }
 
+    /// Withdraws part of a goal's principal, charging the early break fee on
+    /// that portion only; the goal stays open
+    pub fn partial_withdraw_goal_save(env: Env, user: Address, goal_id: u64, amount: i128) -> i128 {
+        ensure_not_paused(&env).unwrap_or_else(|e| panic_with_error!(&env, e));
+        goal::partial_withdraw_goal_save(&env, user, goal_id, amount)
+            .unwrap_or_else(|e| panic_with_error!(&env, e))
+    }
+
     pub fn get_goal_save_detail(env: Env, goal_id: u64) -> GoalSave {
         goal::get_goal_save(&env, goal_id)
             .unwrap_or_else(|| panic_with_error!(&env, SavingsError::PlanNotFound))
     }
 
+    /// Returns a goal's summary view, including interest accrued so far
+    pub fn get_goal_save_view(env: Env, goal_id: u64) -> Result<GoalSaveView, SavingsError> {
+        views::get_goal_save_view(&env, goal_id)
+    }
+
+    /// Deposit needed every `period_secs` for a goal to reach its target by
+    /// its deadline, including the platform fee
+    pub fn get_goal_required_contribution(
+        env: Env,
+        goal_id: u64,
+        period_secs: u64,
+    ) -> Result<i128, SavingsError> {
+        views::get_goal_required_contribution(&env, goal_id, period_secs)
+    }
+
     pub fn get_user_goal_saves(env: Env, user: Address) -> Vec<u64> {
         goal::get_user_goal_saves(&env, &user)
     }
 
     // --- Group Save Logic ---
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_group_save(
         env: Env,
         creator: Address,
@@ -360,9 +541,11 @@ impl NesteraContract {
         end_time: u64,
     ) -> Result<u64, SavingsError> {
         ensure_not_paused(&env)?;
+        let asset = config::get_settlement_asset(&env)?;
         group::create_group_save(
             &env,
             creator,
+            asset,
             title,
             description,
             category,
@@ -375,11 +558,193 @@ impl NesteraContract {
         )
     }
 
+    /// Creates a rotating savings (ROSCA) group in the settlement asset.
+    /// `payout_order` fixes who receives each cycle's pot; `None` randomises
+    /// it when the rotation starts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_rosca_group(
+        env: Env,
+        creator: Address,
+        title: String,
+        description: String,
+        category: String,
+        contribution_amount: i128,
+        cycle_seconds: u64,
+        is_public: bool,
+        payout_order: Option<Vec<Address>>,
+    ) -> Result<u64, SavingsError> {
+        ensure_not_paused(&env)?;
+        let asset = config::get_settlement_asset(&env)?;
+        rosca::create_rosca_group(
+            &env,
+            creator,
+            asset,
+            title,
+            description,
+            category,
+            contribution_amount,
+            cycle_seconds,
+            is_public,
+            payout_order,
+        )
+    }
+
+    /// Starts a ROSCA group's rotation, freezing its members, and returns
+    /// the payout order
+    pub fn start_rosca(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+    ) -> Result<Vec<Address>, SavingsError> {
+        ensure_not_paused(&env)?;
+        rosca::start_rosca(&env, creator, group_id)
+    }
+
+    /// Pays the current cycle's pot to the next member in the payout order
+    pub fn payout_rosca_cycle(env: Env, group_id: u64) -> Result<Address, SavingsError> {
+        ensure_not_paused(&env)?;
+        rosca::payout_rosca_cycle(&env, group_id)
+    }
+
+    /// Cancels a ROSCA rotation whose current cycle missed its deadline,
+    /// refunding that cycle's contributions
+    pub fn cancel_rosca(env: Env, creator: Address, group_id: u64) -> Result<i128, SavingsError> {
+        ensure_not_paused(&env)?;
+        rosca::cancel_rosca(&env, creator, group_id)
+    }
+
+    pub fn get_rosca_state(env: Env, group_id: u64) -> Option<RoscaState> {
+        rosca::get_rosca_state(&env, group_id)
+    }
+
+    pub fn set_group_asset(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        group::set_group_asset(&env, creator, group_id, asset)
+    }
+
+    pub fn set_group_period(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        period_seconds: u64,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        group::set_group_period(&env, creator, group_id, period_seconds)
+    }
+
+    pub fn set_group_max_members(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        max_members: u32,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        group::set_group_max_members(&env, creator, group_id, max_members)
+    }
+
     pub fn join_group_save(env: Env, user: Address, group_id: u64) -> Result<(), SavingsError> {
         ensure_not_paused(&env)?;
         group::join_group_save(&env, user, group_id)
     }
 
+    /// Invites `invitee` to a group; they join with `join_group_save`
+    pub fn invite_to_group(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        invitee: Address,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::invite_to_group(&env, creator, group_id, invitee)
+    }
+
+    pub fn revoke_group_invite(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        invitee: Address,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::revoke_group_invite(&env, creator, group_id, invitee)
+    }
+
+    /// Registers a single-use invite code for a group, as the SHA-256 hash
+    /// of the code followed by the invitee's XDR-encoded address
+    pub fn add_group_invite_code(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        code_hash: BytesN<32>,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::add_group_invite_code(&env, creator, group_id, code_hash)
+    }
+
+    /// Joins a group by redeeming an invite code issued to `user`
+    pub fn join_group_with_code(
+        env: Env,
+        user: Address,
+        group_id: u64,
+        code: Bytes,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::join_group_with_code(&env, user, group_id, code)
+    }
+
+    /// Sets whether joining a public group needs the creator's approval
+    pub fn set_group_join_approval(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        required: bool,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::set_group_join_approval(&env, creator, group_id, required)
+    }
+
+    /// Asks to join a public group that requires the creator's approval
+    pub fn request_to_join_group(
+        env: Env,
+        user: Address,
+        group_id: u64,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::request_to_join_group(&env, user, group_id)
+    }
+
+    /// Accepts (adding the user as a member) or rejects a pending join request
+    pub fn respond_to_join_request(
+        env: Env,
+        creator: Address,
+        group_id: u64,
+        user: Address,
+        accept: bool,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        invites::respond_to_join_request(&env, creator, group_id, user, accept)
+    }
+
+    pub fn get_group_invites(env: Env, group_id: u64) -> Vec<Address> {
+        invites::get_group_invites(&env, group_id)
+    }
+
+    pub fn get_group_invite_codes(env: Env, group_id: u64) -> Vec<BytesN<32>> {
+        invites::get_group_invite_codes(&env, group_id)
+    }
+
+    pub fn get_group_join_requests(env: Env, group_id: u64) -> Vec<Address> {
+        invites::get_group_join_requests(&env, group_id)
+    }
+
+    pub fn group_requires_approval(env: Env, group_id: u64) -> bool {
+        invites::requires_approval(&env, group_id)
+    }
+
     pub fn contribute_to_group_save(
         env: Env,
         user: Address,
@@ -395,6 +760,38 @@ impl NesteraContract {
         group::break_group_save(&env, user, group_id)
     }
 
+    /// Pays a member their share of a completed group's interest
+    pub fn claim_group_interest(
+        env: Env,
+        user: Address,
+        group_id: u64,
+    ) -> Result<i128, SavingsError> {
+        group::claim_group_interest(&env, user, group_id)
+    }
+
+    /// Returns a member's principal once their group has completed
+    pub fn withdraw_completed_group_save(
+        env: Env,
+        user: Address,
+        group_id: u64,
+    ) -> Result<i128, SavingsError> {
+        group::withdraw_completed_group_save(&env, user, group_id)
+    }
+
+    /// Returns the interest a group pool has earned so far
+    pub fn get_group_accrued_interest(env: Env, group_id: u64) -> Result<i128, SavingsError> {
+        group::get_group_accrued_interest(&env, group_id)
+    }
+
+    /// Returns a member's share of a group's interest so far
+    pub fn get_group_interest_share(
+        env: Env,
+        group_id: u64,
+        user: Address,
+    ) -> Result<i128, SavingsError> {
+        group::get_member_interest_share(&env, group_id, &user)
+    }
+
     // --- Admin Control Functions ---
 
     pub fn set_admin(
@@ -449,6 +846,36 @@ impl NesteraContract {
         Ok(())
     }
 
+    /// Sets the penalty for breaking a lock, in bps of principal at the start
+    /// of the lock; it shrinks linearly to zero at maturity
+    pub fn set_lock_break_penalty_bps(env: Env, bps: u32) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if bps > 10_000 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::LockBreakPenaltyBps, &bps);
+        env.events().publish((symbol_short!("set_lbrk"),), bps);
+        Ok(())
+    }
+
+    /// Sets how many consecutive failed executions deactivate an AutoSave
+    pub fn set_autosave_max_failures(env: Env, max_failures: u32) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        if max_failures == 0 {
+            return Err(SavingsError::InvalidAmount);
+        }
+        env.storage()
+            .instance()
+            .set(&AutoSaveDataKey::MaxFailures, &max_failures);
+        env.events()
+            .publish((symbol_short!("set_asfl"),), max_failures);
+        Ok(())
+    }
+
     pub fn set_fee_recipient(env: Env, recipient: Address) -> Result<(), SavingsError> {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
@@ -525,6 +952,16 @@ impl NesteraContract {
         rates::get_lock_rate(&env, duration_days)
     }
 
+    /// Returns the durations (in days) that have a lock rate configured
+    pub fn get_lock_rate_tiers(env: Env) -> Vec<u64> {
+        rates::get_lock_rate_tiers(&env)
+    }
+
+    /// Returns the rate a new lock of `duration` seconds would be created at
+    pub fn get_lock_rate_for_duration(env: Env, duration: u64) -> Result<i128, SavingsError> {
+        rates::get_lock_rate_for_duration(&env, duration)
+    }
+
     pub fn get_early_break_fee_bps(env: Env) -> u32 {
         env.storage()
             .instance()
@@ -532,6 +969,50 @@ impl NesteraContract {
             .unwrap_or(0)
     }
 
+    pub fn get_lock_break_penalty_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LockBreakPenaltyBps)
+            .unwrap_or(0)
+    }
+
+    /// Sets the tip paid to registered keepers per executed AutoSave and the
+    /// most a keeper is paid per asset in one batch
+    pub fn set_keeper_tip(
+        env: Env,
+        tip: KeeperTip,
+        max_per_batch: i128,
+    ) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        let config = KeeperTipConfig { tip, max_per_batch };
+        keeper::set_tip_config(&env, &config)?;
+        env.events().publish((symbol_short!("set_ktip"),), config);
+        Ok(())
+    }
+
+    /// Adds `keeper` to the allowlist of keepers paid AutoSave tips
+    pub fn register_keeper(env: Env, keeper: Address) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        keeper::set_registered(&env, &keeper, true);
+        env.events().publish((symbol_short!("kpr_add"),), keeper);
+        Ok(())
+    }
+
+    /// Removes `keeper` from the allowlist of keepers paid AutoSave tips
+    pub fn remove_keeper(env: Env, keeper: Address) -> Result<(), SavingsError> {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+        keeper::set_registered(&env, &keeper, false);
+        env.events().publish((symbol_short!("kpr_rm"),), keeper);
+        Ok(())
+    }
+
+    pub fn get_autosave_max_failures(env: Env) -> u32 {
+        autosave::get_max_failures(&env)
+    }
+
     pub fn get_fee_recipient(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::FeeRecipient)
     }
@@ -543,11 +1024,86 @@ impl NesteraContract {
             .unwrap_or(0)
     }
 
-    pub fn get_protocol_fee_balance(env: Env, recipient: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::TotalBalance(recipient))
-            .unwrap_or(0)
+    /// Returns the fees in `asset` credited to `recipient` that are still in
+    /// custody (`accrued`) and the total already swept to the treasury (`swept`)
+    pub fn get_protocol_fee_balance(
+        env: Env,
+        recipient: Address,
+        asset: Address,
+    ) -> ProtocolFeeBalance {
+        fees::get_recipient_fee_balance(&env, recipient, asset)
+    }
+
+    /// Returns the accrued and swept fee totals for a single asset
+    pub fn get_asset_fee_balance(env: Env, asset: Address) -> ProtocolFeeBalance {
+        fees::get_asset_fee_balance(&env, asset)
+    }
+
+    /// Pays `amount` of accrued fees in `asset` to the configured treasury (admin only)
+    pub fn sweep_fees(env: Env, asset: Address, amount: i128) -> Result<(), SavingsError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(SavingsError::Unauthorized)?;
+        admin.require_auth();
+        fees::sweep_fees(&env, asset, amount)
+    }
+
+    // ========== Solvency Functions ==========
+
+    /// Compares recorded liabilities against holdings in every tracked asset,
+    /// failing with `InvariantViolation` if any asset is short
+    pub fn check_solvency(env: Env) -> Result<Vec<SolvencyReport>, SavingsError> {
+        ledger::check_solvency(&env)
+    }
+
+    /// Returns the liabilities and holdings recorded for a single asset
+    pub fn get_solvency_report(env: Env, asset: Address) -> Result<SolvencyReport, SavingsError> {
+        ledger::get_solvency_report(&env, asset)
+    }
+
+    // ========== Yield Strategy Functions ==========
+
+    /// Registers the yield strategy for an asset with its allocation cap and
+    /// liquid buffer, both in bps of Flexi/Lock liquidity (admin only)
+    pub fn set_strategy(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        strategy: Address,
+        max_allocation_bps: u32,
+        buffer_bps: u32,
+    ) -> Result<(), SavingsError> {
+        strategy::set_strategy(&env, admin, asset, strategy, max_allocation_bps, buffer_bps)
+    }
+
+    /// Allocates idle liquidity in an asset to its strategy (admin only)
+    pub fn allocate_to_strategy(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<(), SavingsError> {
+        strategy::allocate_to_strategy(&env, admin, asset, amount)
+    }
+
+    /// Recalls funds from an asset's strategy back into custody (admin only)
+    pub fn recall_from_strategy(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        amount: i128,
+    ) -> Result<i128, SavingsError> {
+        strategy::recall_from_strategy(&env, admin, asset, amount)
+    }
+
+    /// Returns the strategy position for an asset
+    pub fn get_strategy_allocation(
+        env: Env,
+        asset: Address,
+    ) -> Result<StrategyAllocation, SavingsError> {
+        strategy::get_strategy_allocation(&env, asset)
     }
 
     // ========== Rewards Functions ==========
@@ -661,28 +1217,66 @@ impl NesteraContract {
 
     // ========== AutoSave Functions ==========
 
-    /// Creates a new AutoSave schedule for recurring Flexi deposits
+    /// Creates a new AutoSave schedule for recurring deposits into `destination`
+    #[allow(clippy::too_many_arguments)]
     pub fn create_autosave(
         env: Env,
         user: Address,
         amount: i128,
         interval_seconds: u64,
         start_time: u64,
+        destination: AutoSaveDestination,
+        allowance_expiry_ledger: u32,
+        missed_policy: MissedIntervalPolicy,
     ) -> Result<u64, SavingsError> {
         ensure_not_paused(&env)?;
-        autosave::create_autosave(&env, user, amount, interval_seconds, start_time)
+        autosave::create_autosave(
+            &env,
+            user,
+            amount,
+            interval_seconds,
+            start_time,
+            destination,
+            allowance_expiry_ledger,
+            missed_policy,
+        )
     }
 
-    /// Executes an AutoSave schedule if it's due
-    pub fn execute_autosave(env: Env, schedule_id: u64) -> Result<(), SavingsError> {
+    /// Executes an AutoSave schedule if it's due, returning the number of
+    /// intervals deposited
+    pub fn execute_autosave(env: Env, schedule_id: u64) -> Result<u32, SavingsError> {
         ensure_not_paused(&env)?;
         autosave::execute_autosave(&env, schedule_id)
     }
 
     /// Batch-executes multiple due AutoSave schedules in a single call.
     /// Returns a Vec<bool> indicating success (true) or skip/failure (false) per schedule.
-    pub fn execute_due_autosaves(env: Env, schedule_ids: Vec<u64>) -> Vec<bool> {
-        autosave::execute_due_autosaves(&env, schedule_ids)
+    /// A registered `keeper` is tipped for the schedules it executes.
+    pub fn execute_due_autosaves(
+        env: Env,
+        keeper: Option<Address>,
+        schedule_ids: Vec<u64>,
+    ) -> Vec<bool> {
+        autosave::execute_due_autosaves(&env, keeper, schedule_ids)
+    }
+
+    /// Returns up to `limit` IDs of AutoSave schedules due at `now`, earliest
+    /// first
+    pub fn get_due_autosaves(env: Env, now: u64, limit: u32) -> Vec<u64> {
+        autosave::get_due_autosaves(&env, now, limit)
+    }
+
+    /// Returns the tips `keeper` has been paid for AutoSave batches, per asset
+    pub fn get_keeper_earnings(env: Env, keeper: Address) -> Vec<KeeperEarning> {
+        keeper::get_earnings(&env, &keeper)
+    }
+
+    pub fn is_keeper(env: Env, keeper: Address) -> bool {
+        keeper::is_registered(&env, &keeper)
+    }
+
+    pub fn get_keeper_tip(env: Env) -> KeeperTipConfig {
+        keeper::get_tip_config(&env)
     }
 
     /// Cancels an AutoSave schedule
@@ -691,13 +1285,48 @@ impl NesteraContract {
         autosave::cancel_autosave(&env, user, schedule_id)
     }
 
+    /// Pauses an active AutoSave schedule
+    pub fn pause_autosave(env: Env, user: Address, schedule_id: u64) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        autosave::pause_autosave(&env, user, schedule_id)
+    }
+
+    /// Resumes a paused or deactivated AutoSave schedule, returning its next
+    /// execution time, one interval from now
+    pub fn resume_autosave(env: Env, user: Address, schedule_id: u64) -> Result<u64, SavingsError> {
+        ensure_not_paused(&env)?;
+        autosave::resume_autosave(&env, user, schedule_id)
+    }
+
+    /// Changes the amount and interval of an AutoSave schedule
+    pub fn update_autosave(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        amount: i128,
+        interval_seconds: u64,
+    ) -> Result<(), SavingsError> {
+        ensure_not_paused(&env)?;
+        autosave::update_autosave(&env, user, schedule_id, amount, interval_seconds)
+    }
+
+    /// Records the expiry ledger of a renewed token allowance for a schedule
+    pub fn set_autosave_allowance_expiry(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        allowance_expiry_ledger: u32,
+    ) -> Result<(), SavingsError> {
+        autosave::set_autosave_allowance_expiry(&env, user, schedule_id, allowance_expiry_ledger)
+    }
+
     /// Gets an AutoSave schedule by ID
     pub fn get_autosave(env: Env, schedule_id: u64) -> Option<AutoSave> {
         autosave::get_autosave(&env, schedule_id)
     }
 
-    /// Gets all AutoSave schedule IDs for a user
-    pub fn get_user_autosaves(env: Env, user: Address) -> Vec<u64> {
+    /// Gets all AutoSave schedules for a user, with their current status
+    pub fn get_user_autosaves(env: Env, user: Address) -> Vec<AutoSave> {
         autosave::get_user_autosaves(&env, &user)
     }
 
@@ -736,6 +1365,43 @@ impl NesteraContract {
         config::set_protocol_fee(&env, admin, new_fee_bps)
     }
 
+    /// Sets the settlement asset for Flexi savings (admin only, one-time)
+    pub fn set_settlement_asset(
+        env: Env,
+        admin: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        config::set_settlement_asset(&env, admin, asset)
+    }
+
+    /// Adds an asset contract to the savings allowlist (admin only)
+    pub fn add_supported_asset(
+        env: Env,
+        admin: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        assets::add_supported_asset(&env, admin, asset)
+    }
+
+    /// Removes an asset contract from the savings allowlist (admin only)
+    pub fn remove_supported_asset(
+        env: Env,
+        admin: Address,
+        asset: Address,
+    ) -> Result<(), SavingsError> {
+        assets::remove_supported_asset(&env, admin, asset)
+    }
+
+    /// Returns the assets currently accepted for new deposits
+    pub fn get_supported_assets(env: Env) -> Vec<Address> {
+        assets::get_supported_assets(&env)
+    }
+
+    /// Returns the user's open positions grouped by asset
+    pub fn get_user_holdings(env: Env, user: Address) -> Vec<AssetHolding> {
+        assets::get_user_holdings(&env, &user)
+    }
+
     /// Pauses the contract via config module (admin only)
     pub fn pause_contract(env: Env, admin: Address) -> Result<(), SavingsError> {
         config::pause_contract(&env, admin)
@@ -1,20 +1,40 @@
+use crate::assets::{self, Funding};
 use crate::ensure_not_paused;
 use crate::errors::SavingsError;
+use crate::fees;
+use crate::ledger::{self, Liability};
+use crate::math::{self, Rounding};
+use crate::rates;
 use crate::rewards::storage;
 use crate::storage_types::{DataKey, LockSave, User};
 use crate::ttl;
 use crate::users;
-use soroban_sdk::{symbol_short, Address, Env, Vec};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
 
-/// Creates a new Lock Save plan for a user
+/// Creates a new Lock Save plan for a user, pulling `amount` of `asset`
+/// into contract custody until maturity.
 pub fn create_lock_save(
     env: &Env,
     user: Address,
+    asset: Address,
     amount: i128,
     duration: u64,
 ) -> Result<u64, SavingsError> {
-    ensure_not_paused(env)?;
     // Note: user.require_auth() is already called in lib.rs wrapper function
+    create_lock_save_funded(env, user, asset, amount, duration, Funding::Signed)
+}
+
+/// Lock creation where `funding` selects how the principal is pulled from
+/// the user's wallet.
+pub(crate) fn create_lock_save_funded(
+    env: &Env,
+    user: Address,
+    asset: Address,
+    amount: i128,
+    duration: u64,
+    funding: Funding,
+) -> Result<u64, SavingsError> {
+    ensure_not_paused(env)?;
 
     // Validate inputs
     if amount <= 0 {
@@ -24,38 +44,14 @@ pub fn create_lock_save(
         // Aligned with the test expectation of a generic invalid duration error
         return Err(SavingsError::InvalidTimestamp);
     }
+    assets::require_supported_asset(env, &asset)?;
 
     // Ensure user exists using your users module
     if !users::user_exists(env, &user) {
         return Err(SavingsError::UserNotFound);
     }
 
-    // ID Logic
-    let lock_id = get_next_lock_id(env);
-    increment_next_lock_id(env);
-
-    let start_time = env.ledger().timestamp();
-    let maturity_time = start_time
-        .checked_add(duration)
-        .ok_or(SavingsError::Overflow)?;
-
-    let lock_save = LockSave {
-        id: lock_id,
-        owner: user.clone(),
-        amount,
-        interest_rate: 500, // Matching your test expectation of 500 (5%)
-        start_time,
-        maturity_time,
-        is_withdrawn: false,
-    };
-
-    // Store the LockSave
-    env.storage()
-        .persistent()
-        .set(&DataKey::LockSave(lock_id), &lock_save);
-
-    // Update user's lock list
-    add_lock_to_user(env, &user, lock_id);
+    let lock_id = store_new_lock(env, &user, &asset, amount, duration, false)?;
 
     // Update user's profile stats
     let user_key = DataKey::User(user.clone());
@@ -64,6 +60,9 @@ pub fn create_lock_save(
     user_data.savings_count += 1;
     env.storage().persistent().set(&user_key, &user_data);
 
+    ledger::adjust(env, Liability::Locked, &asset, amount)?;
+    assets::pull_funded(env, &asset, &user, amount, funding);
+
     storage::award_deposit_points(env, user.clone(), amount)?;
     storage::award_long_lock_bonus(env, user.clone(), amount, duration)?;
 
@@ -93,7 +92,11 @@ pub fn withdraw_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128
         return Err(SavingsError::TooEarly);
     }
 
-    let final_amount = calculate_lock_save_yield(&lock_save, env.ledger().timestamp());
+    settle_lock_interest(env, &mut lock_save)?;
+    let final_amount = lock_save
+        .amount
+        .checked_add(lock_save.accrued_interest)
+        .ok_or(SavingsError::Overflow)?;
 
     lock_save.is_withdrawn = true;
     env.storage()
@@ -107,6 +110,16 @@ pub fn withdraw_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128
         env.storage().persistent().set(&user_key, &user_data);
     }
 
+    // Yield beyond the principal is paid out of reserves
+    ledger::adjust(env, Liability::Locked, &lock_save.asset, -lock_save.amount)?;
+    ledger::adjust(
+        env,
+        Liability::Interest,
+        &lock_save.asset,
+        -lock_save.accrued_interest,
+    )?;
+    assets::pay(env, &lock_save.asset, &user, final_amount);
+
     // Extend TTL (completed locks get shorter extension)
     ttl::extend_lock_ttl(env, lock_id);
     ttl::extend_user_ttl(env, &user);
@@ -117,6 +130,315 @@ pub fn withdraw_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128
     Ok(final_amount)
 }
 
+/// Closes a lock before maturity, returning principal minus a penalty.
+///
+/// The penalty is `LockBreakPenaltyBps` of the principal scaled by the share
+/// of the lock duration still remaining, and is credited to the
+/// `FeeRecipient`. Any yield accrued on the lock is forfeited.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the lock does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the lock
+/// * `SavingsError::PlanCompleted` - If the lock was already withdrawn
+/// * `SavingsError::TooLate` - If the lock has matured; use `withdraw_lock_save`
+pub fn break_lock_save(env: &Env, user: Address, lock_id: u64) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+
+    let mut lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if lock_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    if check_matured_lock(env, lock_id) {
+        return Err(SavingsError::TooLate);
+    }
+
+    let penalty = calculate_break_penalty(env, &lock_save)?;
+    let net_amount = lock_save
+        .amount
+        .checked_sub(penalty)
+        .ok_or(SavingsError::Underflow)?;
+
+    lock_save.is_withdrawn = true;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+
+    let user_key = DataKey::User(user.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data
+            .total_balance
+            .checked_sub(lock_save.amount)
+            .ok_or(SavingsError::Underflow)?;
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+
+    remove_lock_from_user(env, &user, lock_id);
+
+    // Yield settled by earlier top-ups or extensions is forfeited
+    ledger::adjust(env, Liability::Locked, &lock_save.asset, -lock_save.amount)?;
+    ledger::adjust(
+        env,
+        Liability::Interest,
+        &lock_save.asset,
+        -lock_save.accrued_interest,
+    )?;
+    assets::pay(env, &lock_save.asset, &user, net_amount);
+
+    if let Some(fee_recipient) = fees::accrue_fee(env, &lock_save.asset, penalty)? {
+        env.events()
+            .publish((symbol_short!("lbrk_fee"), fee_recipient, lock_id), penalty);
+    }
+
+    env.events().publish(
+        (symbol_short!("lock_brk"), user.clone(), lock_id),
+        net_amount,
+    );
+
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_user_ttl(env, &user);
+
+    Ok(net_amount)
+}
+
+/// Adds `amount` to an open lock without changing its maturity.
+///
+/// Yield earned so far is settled at the old principal, and the enlarged
+/// principal accrues from now on. The added principal earns deposit points,
+/// plus the long-lock bonus if the lock's term is above the threshold.
+///
+/// # Errors
+/// * `SavingsError::InvalidAmount` - If `amount` is not positive
+/// * `SavingsError::PlanNotFound` - If the lock does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the lock
+/// * `SavingsError::PlanCompleted` - If the lock was already withdrawn
+/// * `SavingsError::TooLate` - If the lock has matured
+pub fn top_up_lock_save(
+    env: &Env,
+    user: Address,
+    lock_id: u64,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    ensure_not_paused(env)?;
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+
+    let mut lock_save = get_open_lock(env, &user, lock_id)?;
+
+    settle_lock_interest(env, &mut lock_save)?;
+    lock_save.amount = lock_save
+        .amount
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+
+    let user_key = DataKey::User(user.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data
+            .total_balance
+            .checked_add(amount)
+            .ok_or(SavingsError::Overflow)?;
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+
+    ledger::adjust(env, Liability::Locked, &lock_save.asset, amount)?;
+    assets::pull(env, &lock_save.asset, &user, amount);
+
+    let duration = lock_save.maturity_time.saturating_sub(lock_save.start_time);
+    storage::award_deposit_points(env, user.clone(), amount)?;
+    storage::award_long_lock_bonus(env, user.clone(), amount, duration)?;
+
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_user_ttl(env, &user);
+
+    env.events()
+        .publish((symbol_short!("lock_top"), user, lock_id), amount);
+
+    Ok(lock_save.amount)
+}
+
+/// Moves a lock's maturity `extra_duration` seconds later.
+///
+/// Yield earned so far is settled at the old rate, and the lock is re-priced
+/// from the `LockRate` table for its new total term. If the extension takes
+/// the term above the long-lock threshold, the bonus is awarded on the
+/// principal.
+///
+/// # Errors
+/// * `SavingsError::InvalidTimestamp` - If `extra_duration` is zero
+/// * `SavingsError::PlanNotFound` - If the lock does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the lock
+/// * `SavingsError::PlanCompleted` - If the lock was already withdrawn
+/// * `SavingsError::TooLate` - If the lock has matured; use `rollover_lock_save`
+pub fn extend_lock_save(
+    env: &Env,
+    user: Address,
+    lock_id: u64,
+    extra_duration: u64,
+) -> Result<u64, SavingsError> {
+    ensure_not_paused(env)?;
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+
+    if extra_duration == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    let mut lock_save = get_open_lock(env, &user, lock_id)?;
+
+    let old_duration = lock_save.maturity_time.saturating_sub(lock_save.start_time);
+    let maturity_time = lock_save
+        .maturity_time
+        .checked_add(extra_duration)
+        .ok_or(SavingsError::Overflow)?;
+    let new_duration = maturity_time.saturating_sub(lock_save.start_time);
+
+    let interest_rate = rates::get_lock_rate_for_duration(env, new_duration)?;
+    let interest_rate =
+        u32::try_from(interest_rate).map_err(|_| SavingsError::InvalidInterestRate)?;
+
+    settle_lock_interest(env, &mut lock_save)?;
+    lock_save.maturity_time = maturity_time;
+    lock_save.interest_rate = interest_rate;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+
+    if old_duration <= storage::LONG_LOCK_BONUS_THRESHOLD_SECS {
+        storage::award_long_lock_bonus(env, user.clone(), lock_save.amount, new_duration)?;
+    }
+
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_user_ttl(env, &user);
+
+    env.events().publish(
+        (symbol_short!("lock_ext"), user, lock_id),
+        (maturity_time, interest_rate),
+    );
+
+    Ok(maturity_time)
+}
+
+/// Opts a lock in or out of automatic rollover at maturity.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the lock does not exist
+/// * `SavingsError::Unauthorized` - If the caller does not own the lock
+/// * `SavingsError::PlanCompleted` - If the lock was already withdrawn
+pub fn set_lock_rollover(
+    env: &Env,
+    user: Address,
+    lock_id: u64,
+    enabled: bool,
+) -> Result<(), SavingsError> {
+    // Note: user.require_auth() is already called in lib.rs wrapper function
+    let mut lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+    if lock_save.owner != user {
+        return Err(SavingsError::Unauthorized);
+    }
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    lock_save.auto_rollover = enabled;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+    ttl::extend_lock_ttl(env, lock_id);
+
+    env.events()
+        .publish((symbol_short!("lock_roll"), user, lock_id), enabled);
+
+    Ok(())
+}
+
+/// Re-locks a matured lock that has opted into rollover. Callable by anyone.
+///
+/// Principal plus the yield earned so far moves into a fresh lock for the
+/// same duration, priced at the current `LockRate` tier. The old lock is
+/// closed and replaced in the owner's lock list.
+///
+/// # Returns
+/// The ID of the new lock
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the lock does not exist
+/// * `SavingsError::PlanCompleted` - If the lock was already withdrawn
+/// * `SavingsError::InvalidPlanConfig` - If the owner has not opted in, or no
+///   LockRate tier covers the duration any more
+/// * `SavingsError::TooEarly` - If the lock has not matured
+pub fn rollover_lock_save(env: &Env, lock_id: u64) -> Result<u64, SavingsError> {
+    ensure_not_paused(env)?;
+
+    let mut lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+    if !lock_save.auto_rollover {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    if !check_matured_lock(env, lock_id) {
+        return Err(SavingsError::TooEarly);
+    }
+
+    settle_lock_interest(env, &mut lock_save)?;
+    let earned = lock_save.accrued_interest;
+    let rolled_amount = lock_save
+        .amount
+        .checked_add(earned)
+        .ok_or(SavingsError::Overflow)?;
+    let duration = lock_save.maturity_time.saturating_sub(lock_save.start_time);
+
+    lock_save.is_withdrawn = true;
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+    remove_lock_from_user(env, &lock_save.owner, lock_id);
+
+    let new_lock_id = store_new_lock(
+        env,
+        &lock_save.owner,
+        &lock_save.asset,
+        rolled_amount,
+        duration,
+        true,
+    )?;
+
+    // The yield stays in custody as part of the new lock's principal
+    let user_key = DataKey::User(lock_save.owner.clone());
+    if let Some(mut user_data) = env.storage().persistent().get::<DataKey, User>(&user_key) {
+        user_data.total_balance = user_data
+            .total_balance
+            .checked_add(earned)
+            .ok_or(SavingsError::Overflow)?;
+        env.storage().persistent().set(&user_key, &user_data);
+    }
+    ledger::adjust(env, Liability::Interest, &lock_save.asset, -earned)?;
+    ledger::adjust(env, Liability::Locked, &lock_save.asset, earned)?;
+
+    ttl::extend_lock_ttl(env, lock_id);
+    ttl::extend_lock_ttl(env, new_lock_id);
+    ttl::extend_user_ttl(env, &lock_save.owner);
+
+    env.events().publish(
+        (Symbol::new(env, "lock_rolled"), lock_save.owner),
+        (lock_id, new_lock_id, rolled_amount),
+    );
+
+    Ok(new_lock_id)
+}
+
 pub fn check_matured_lock(env: &Env, lock_id: u64) -> bool {
     if let Some(lock_save) = get_lock_save(env, lock_id) {
         // Extend TTL on check
@@ -183,12 +505,138 @@ fn add_lock_to_user(env: &Env, user: &Address, lock_id: u64) {
         .set(&DataKey::UserLockSaves(user.clone()), &user_locks);
 }
 
-fn calculate_lock_save_yield(lock_save: &LockSave, current_time: u64) -> i128 {
-    let duration_seconds = current_time.saturating_sub(lock_save.start_time);
-    let duration_years = (duration_seconds as f64) / (365.25 * 24.0 * 3600.0);
-    let rate_decimal = (lock_save.interest_rate as f64) / 10000.0;
-    let multiplier = 1.0 + (rate_decimal * duration_years);
-    (lock_save.amount as f64 * multiplier) as i128
+/// Prices and stores a fresh lock starting now, adding it to the owner's list.
+fn store_new_lock(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+    duration: u64,
+    auto_rollover: bool,
+) -> Result<u64, SavingsError> {
+    // Price the lock from the LockRate table; the rate is fixed for its lifetime
+    let interest_rate = rates::get_lock_rate_for_duration(env, duration)?;
+    let interest_rate =
+        u32::try_from(interest_rate).map_err(|_| SavingsError::InvalidInterestRate)?;
+
+    // ID Logic
+    let lock_id = get_next_lock_id(env);
+    increment_next_lock_id(env);
+
+    let start_time = env.ledger().timestamp();
+    let maturity_time = start_time
+        .checked_add(duration)
+        .ok_or(SavingsError::Overflow)?;
+
+    let lock_save = LockSave {
+        id: lock_id,
+        owner: user.clone(),
+        asset: asset.clone(),
+        amount,
+        interest_rate,
+        start_time,
+        maturity_time,
+        is_withdrawn: false,
+        accrued_interest: 0,
+        last_accrual: start_time,
+        auto_rollover,
+    };
+
+    // Store the LockSave
+    env.storage()
+        .persistent()
+        .set(&DataKey::LockSave(lock_id), &lock_save);
+
+    // Update user's lock list
+    add_lock_to_user(env, user, lock_id);
+
+    Ok(lock_id)
+}
+
+/// Loads a lock `user` owns that is still open and has not matured.
+fn get_open_lock(env: &Env, user: &Address, lock_id: u64) -> Result<LockSave, SavingsError> {
+    let lock_save = get_lock_save(env, lock_id).ok_or(SavingsError::PlanNotFound)?;
+
+    if lock_save.owner != *user {
+        return Err(SavingsError::Unauthorized);
+    }
+    if lock_save.is_withdrawn {
+        return Err(SavingsError::PlanCompleted);
+    }
+    if env.ledger().timestamp() >= lock_save.maturity_time {
+        return Err(SavingsError::TooLate);
+    }
+
+    Ok(lock_save)
+}
+
+fn remove_lock_from_user(env: &Env, user: &Address, lock_id: u64) {
+    let user_locks = get_user_lock_saves(env, user);
+    let mut new_locks = Vec::new(env);
+
+    for id in user_locks.iter() {
+        if id != lock_id {
+            new_locks.push_back(id);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::UserLockSaves(user.clone()), &new_locks);
+}
+
+/// Penalty for breaking `lock_save` now: the configured bps of the principal,
+/// scaled by the fraction of the lock duration that remains.
+fn calculate_break_penalty(env: &Env, lock_save: &LockSave) -> Result<i128, SavingsError> {
+    let penalty_bps: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::LockBreakPenaltyBps)
+        .unwrap_or(0);
+
+    let duration = lock_save.maturity_time.saturating_sub(lock_save.start_time);
+    let remaining = lock_save
+        .maturity_time
+        .saturating_sub(env.ledger().timestamp());
+    if penalty_bps == 0 || duration == 0 || remaining == 0 {
+        return Ok(0);
+    }
+
+    // amount * bps * remaining / (10_000 * duration), rounded in the user's favour
+    let scaled_bps = (penalty_bps as i128)
+        .checked_mul(remaining as i128)
+        .ok_or(SavingsError::Overflow)?;
+    let denominator = math::BPS_DENOMINATOR
+        .checked_mul(duration as i128)
+        .ok_or(SavingsError::Overflow)?;
+    math::mul_div(lock_save.amount, scaled_bps, denominator, Rounding::Down)
+}
+
+/// Yield earned on the current principal and rate from `last_accrual` up to
+/// maturity.
+fn pending_lock_interest(lock_save: &LockSave, current_time: u64) -> Result<i128, SavingsError> {
+    let duration_seconds = current_time
+        .min(lock_save.maturity_time)
+        .saturating_sub(lock_save.last_accrual);
+    math::simple_interest(
+        lock_save.amount,
+        lock_save.interest_rate as i128,
+        duration_seconds,
+        Rounding::Down,
+    )
+}
+
+/// Folds pending yield into `accrued_interest`, booking it as owed, so the
+/// principal or rate can change.
+fn settle_lock_interest(env: &Env, lock_save: &mut LockSave) -> Result<(), SavingsError> {
+    let now = env.ledger().timestamp();
+    let pending = pending_lock_interest(lock_save, now)?;
+    lock_save.accrued_interest = lock_save
+        .accrued_interest
+        .checked_add(pending)
+        .ok_or(SavingsError::Overflow)?;
+    lock_save.last_accrual = now;
+    ledger::adjust(env, Liability::Interest, &lock_save.asset, pending)
 }
 
 #[cfg(test)]
@@ -198,6 +646,7 @@ mod tests {
     use crate::{NesteraContract, NesteraContractClient};
     use soroban_sdk::{
         testutils::{Address as _, Events, Ledger},
+        token::{StellarAssetClient, TokenClient},
         Address, BytesN, Env, IntoVal, Symbol,
     };
 
@@ -226,9 +675,24 @@ mod tests {
         };
         assert!(client.try_initialize_rewards_config(&config).is_ok());
 
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+        client.set_lock_rate(&admin, &0, &500);
+
+        // Seed a reserve so matured locks can be paid their yield
+        StellarAssetClient::new(&env, &token).mint(&client.address, &1_000_000);
+
         (env, client, admin)
     }
 
+    /// Mints settlement asset tokens to `user` so lock deposits can settle
+    fn fund(env: &Env, client: &NesteraContractClient, user: &Address) {
+        let token = client.get_config().settlement_asset.unwrap();
+        StellarAssetClient::new(env, &token).mint(user, &1_000_000);
+    }
+
     fn setup_env_with_rewards() -> (Env, NesteraContractClient<'static>, Address) {
         setup_env_with_rewards_enabled(true)
     }
@@ -282,6 +746,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let amount = 1_000i128;
         let above_threshold = LONG_LOCK_BONUS_THRESHOLD_SECS + 1;
@@ -300,6 +765,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let amount = 1_000i128;
         client.create_lock_save(&user, &amount, &LONG_LOCK_BONUS_THRESHOLD_SECS);
@@ -316,6 +782,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let amount = 1_000i128;
         let below_threshold = LONG_LOCK_BONUS_THRESHOLD_SECS - 1;
@@ -333,6 +800,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let amount = 1_000i128;
         let above_threshold = LONG_LOCK_BONUS_THRESHOLD_SECS + 1;
@@ -349,6 +817,7 @@ mod tests {
 
         env.mock_all_auths();
         client.initialize_user(&user);
+        fund(&env, &client, &user);
 
         let amount = 1_000i128;
         let duration = LONG_LOCK_BONUS_THRESHOLD_SECS + 1;
@@ -364,4 +833,293 @@ mod tests {
         // base points = 1000 * 10 = 10000, bonus = 2000
         assert_eq!(rewards.total_points, 12_000);
     }
+
+    #[test]
+    fn test_lock_save_holds_principal_and_pays_yield() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let duration = 365 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+        assert_eq!(token.balance(&user), 990_000);
+        assert_eq!(client.get_lock_save_detail(&lock_id).asset, token.address);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = li.timestamp + duration;
+        });
+
+        let payout = client.withdraw_lock_save(&user, &lock_id);
+        assert!(payout > 10_000);
+        assert_eq!(token.balance(&user), 990_000 + payout);
+    }
+
+    #[test]
+    fn test_break_lock_save_penalty_scales_with_remaining_time() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        client.set_fee_recipient(&fee_recipient);
+        client.set_lock_break_penalty_bps(&1_000); // 10% at the start
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let duration = 100 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+        let other_lock = client.create_lock_save(&user, &1_000, &duration);
+
+        // A quarter of the lock served: 10% * 3/4 = 7.5% penalty, no yield
+        env.ledger().with_mut(|li| li.timestamp += duration / 4);
+        assert_eq!(client.break_lock_save(&user, &lock_id), 9_250);
+        assert_eq!(token.balance(&user), 1_000_000 - 1_000 - 750);
+        assert_eq!(
+            client
+                .get_protocol_fee_balance(&fee_recipient, &token.address)
+                .accrued,
+            750
+        );
+
+        assert!(client.get_lock_save_detail(&lock_id).is_withdrawn);
+        assert_eq!(
+            client.get_user_lock_saves(&user),
+            soroban_sdk::vec![&env, other_lock]
+        );
+        assert_eq!(client.get_user(&user).total_balance, 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #23)")]
+    fn test_break_lock_save_twice_fails() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let lock_id = client.create_lock_save(&user, &1_000, &(30 * 24 * 60 * 60));
+        // Without a configured penalty the principal comes back in full
+        assert_eq!(client.break_lock_save(&user, &lock_id), 1_000);
+        client.break_lock_save(&user, &lock_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #52)")]
+    fn test_break_matured_lock_fails() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let duration = 30 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &1_000, &duration);
+        env.ledger().with_mut(|li| li.timestamp += duration);
+        client.break_lock_save(&user, &lock_id);
+    }
+
+    #[test]
+    fn test_rollover_relocks_principal_and_yield_at_current_rate() {
+        let (env, client, admin) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let duration = 365 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+        client.set_lock_rollover(&user, &lock_id, &true);
+        assert!(client.get_lock_save_detail(&lock_id).auto_rollover);
+
+        // The tier is repriced before the lock matures
+        client.set_lock_rate(&admin, &0, &800);
+        env.ledger().with_mut(|li| li.timestamp += duration);
+        let now = env.ledger().timestamp();
+
+        let new_id = client.rollover_lock_save(&lock_id);
+        assert_eq!(
+            env.events().all().last().unwrap().1,
+            (Symbol::new(&env, "lock_rolled"), user.clone()).into_val(&env)
+        );
+
+        assert_ne!(new_id, lock_id);
+        assert!(client.get_lock_save_detail(&lock_id).is_withdrawn);
+        let rolled = client.get_lock_save_detail(&new_id);
+        assert_eq!(rolled.owner, user);
+        assert_eq!(rolled.amount, 10_500);
+        assert_eq!(rolled.interest_rate, 800);
+        assert_eq!(rolled.start_time, now);
+        assert_eq!(rolled.maturity_time, now + duration);
+        assert!(rolled.auto_rollover);
+
+        assert_eq!(
+            client.get_user_lock_saves(&user),
+            soroban_sdk::vec![&env, new_id]
+        );
+        assert_eq!(client.get_user(&user).total_balance, 10_500);
+        let token = client.get_config().settlement_asset.unwrap();
+        assert_eq!(client.get_solvency_report(&token).locked, 10_500);
+    }
+
+    #[test]
+    fn test_late_rollover_and_withdrawal_earn_nothing_past_maturity() {
+        let (env, client, _admin) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let duration = 365 * 24 * 60 * 60;
+        let rolled_id = client.create_lock_save(&user, &10_000, &duration);
+        client.set_lock_rollover(&user, &rolled_id, &true);
+        let withdrawn_id = client.create_lock_save(&user, &10_000, &duration);
+
+        // Called three years after maturity
+        env.ledger().with_mut(|li| li.timestamp += 4 * duration);
+
+        let new_id = client.rollover_lock_save(&rolled_id);
+        assert_eq!(client.get_lock_save_detail(&new_id).amount, 10_500);
+
+        let before = token.balance(&user);
+        assert_eq!(client.withdraw_lock_save(&user, &withdrawn_id), 10_500);
+        assert_eq!(token.balance(&user), before + 10_500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #25)")]
+    fn test_rollover_requires_opt_in() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let duration = 30 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &1_000, &duration);
+        client.set_lock_rollover(&user, &lock_id, &true);
+        client.set_lock_rollover(&user, &lock_id, &false);
+        env.ledger().with_mut(|li| li.timestamp += duration);
+        client.rollover_lock_save(&lock_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #51)")]
+    fn test_rollover_before_maturity_fails() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let lock_id = client.create_lock_save(&user, &1_000, &(30 * 24 * 60 * 60));
+        client.set_lock_rollover(&user, &lock_id, &true);
+        client.rollover_lock_save(&lock_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1)")]
+    fn test_only_owner_can_toggle_rollover() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let lock_id = client.create_lock_save(&user, &1_000, &(30 * 24 * 60 * 60));
+        client.set_lock_rollover(&other, &lock_id, &true);
+    }
+
+    #[test]
+    fn test_top_up_blends_accrued_yield() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let duration = 365 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &10_000, &duration);
+
+        // Half a year at 5% on 10_000 is settled before the top-up
+        env.ledger().with_mut(|li| li.timestamp += duration / 2);
+        assert_eq!(client.top_up_lock_save(&user, &lock_id, &10_000), 20_000);
+        let lock_save = client.get_lock_save_detail(&lock_id);
+        assert_eq!(lock_save.accrued_interest, 250);
+        assert_eq!(lock_save.last_accrual, env.ledger().timestamp());
+        assert_eq!(token.balance(&user), 980_000);
+        assert_eq!(client.get_user(&user).total_balance, 20_000);
+        assert_eq!(client.get_solvency_report(&token.address).locked, 20_000);
+
+        // The remaining half year accrues on the enlarged principal
+        env.ledger().with_mut(|li| li.timestamp += duration / 2);
+        assert_eq!(client.withdraw_lock_save(&user, &lock_id), 20_750);
+    }
+
+    #[test]
+    fn test_extend_reprices_and_awards_long_lock_bonus() {
+        let (env, client, admin) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+        client.set_lock_rate(&admin, &180, &1_000);
+
+        let day = 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &1_000, &(90 * day));
+        assert_eq!(client.get_user_rewards(&user).total_points, 10_000);
+
+        env.ledger().with_mut(|li| li.timestamp += 30 * day);
+        let maturity = client.extend_lock_save(&user, &lock_id, &(120 * day));
+
+        let lock_save = client.get_lock_save_detail(&lock_id);
+        assert_eq!(maturity, lock_save.start_time + 210 * day);
+        assert_eq!(lock_save.maturity_time, maturity);
+        assert_eq!(lock_save.interest_rate, 1_000);
+        assert_eq!(lock_save.accrued_interest, 4);
+        // The term now crosses the threshold: 20% bonus on 10_000 base points
+        assert_eq!(client.get_user_rewards(&user).total_points, 12_000);
+
+        // A further extension does not award the bonus again
+        client.extend_lock_save(&user, &lock_id, &day);
+        assert_eq!(client.get_user_rewards(&user).total_points, 12_000);
+
+        env.ledger().with_mut(|li| li.timestamp = maturity);
+        assert!(!client.check_matured_lock(&lock_id));
+        env.ledger().with_mut(|li| li.timestamp = maturity + day);
+        assert_eq!(client.withdraw_lock_save(&user, &lock_id), 1_000 + 4 + 49);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #52)")]
+    fn test_top_up_matured_lock_fails() {
+        let (env, client, _) = setup_env_with_rewards();
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize_user(&user);
+        fund(&env, &client, &user);
+
+        let duration = 30 * 24 * 60 * 60;
+        let lock_id = client.create_lock_save(&user, &1_000, &duration);
+        env.ledger().with_mut(|li| li.timestamp += duration);
+        client.top_up_lock_save(&user, &lock_id, &1_000);
+    }
 }
@@ -0,0 +1,231 @@
+use crate::errors::SavingsError;
+
+/// Basis point denominator (100% = 10_000 bps)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Seconds in the 365-day year that annual rates are quoted against
+pub const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+/// Direction to round a result that is not an exact integer.
+///
+/// Rounding applies to the magnitude of the result: `Down` truncates toward
+/// zero and `Up` rounds away from zero.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Computes `a * b / denominator` without intermediate overflow.
+///
+/// The product is held in 256 bits, so the call only fails if the final
+/// result does not fit in an `i128`.
+///
+/// # Errors
+/// * `SavingsError::InternalError` - If `denominator` is zero
+/// * `SavingsError::Overflow` - If the result does not fit in an `i128`
+pub fn mul_div(
+    a: i128,
+    b: i128,
+    denominator: i128,
+    rounding: Rounding,
+) -> Result<i128, SavingsError> {
+    if denominator == 0 {
+        return Err(SavingsError::InternalError);
+    }
+
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let (hi, lo) = widening_mul(a.unsigned_abs(), b.unsigned_abs());
+    let (mut quotient, remainder) = div_wide(hi, lo, denominator.unsigned_abs())?;
+
+    if rounding == Rounding::Up && remainder != 0 {
+        quotient = quotient.checked_add(1).ok_or(SavingsError::Overflow)?;
+    }
+
+    if negative {
+        // i128::MIN has no positive counterpart, so allow one extra unit
+        if quotient > i128::MIN.unsigned_abs() {
+            return Err(SavingsError::Overflow);
+        }
+        Ok(0i128.wrapping_sub_unsigned(quotient))
+    } else {
+        i128::try_from(quotient).map_err(|_| SavingsError::Overflow)
+    }
+}
+
+/// Applies a basis point rate to `amount`.
+pub fn apply_bps(amount: i128, bps: i128, rounding: Rounding) -> Result<i128, SavingsError> {
+    mul_div(amount, bps, BPS_DENOMINATOR, rounding)
+}
+
+/// Simple interest on `principal` at an annual `rate_bps` over `elapsed_seconds`.
+pub fn simple_interest(
+    principal: i128,
+    rate_bps: i128,
+    elapsed_seconds: u64,
+    rounding: Rounding,
+) -> Result<i128, SavingsError> {
+    let rate_time = rate_bps
+        .checked_mul(elapsed_seconds as i128)
+        .ok_or(SavingsError::Overflow)?;
+    let denominator = BPS_DENOMINATOR
+        .checked_mul(SECONDS_PER_YEAR)
+        .ok_or(SavingsError::Overflow)?;
+    mul_div(principal, rate_time, denominator, rounding)
+}
+
+/// Full 128 x 128 -> 256 bit unsigned multiplication, returned as (high, low).
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let low = (cross << 64) | (lo_lo & MASK);
+    let high = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (cross >> 64);
+    (high, low)
+}
+
+/// Divides the 256-bit value (high, low) by `divisor`, returning
+/// (quotient, remainder). Fails if the quotient needs more than 128 bits.
+fn div_wide(high: u128, low: u128, divisor: u128) -> Result<(u128, u128), SavingsError> {
+    if high == 0 {
+        return Ok((low / divisor, low % divisor));
+    }
+    if high >= divisor {
+        return Err(SavingsError::Overflow);
+    }
+
+    // Restoring long division, one bit of `low` at a time
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    Ok((quotient, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_div_basic_and_rounding() {
+        assert_eq!(mul_div(10, 3, 4, Rounding::Down), Ok(7));
+        assert_eq!(mul_div(10, 3, 4, Rounding::Up), Ok(8));
+        assert_eq!(mul_div(12, 3, 4, Rounding::Up), Ok(9));
+        assert_eq!(mul_div(0, 3, 4, Rounding::Up), Ok(0));
+    }
+
+    #[test]
+    fn test_mul_div_rounds_magnitude_of_negative_results() {
+        assert_eq!(mul_div(-10, 3, 4, Rounding::Down), Ok(-7));
+        assert_eq!(mul_div(-10, 3, 4, Rounding::Up), Ok(-8));
+        assert_eq!(mul_div(10, -3, -4, Rounding::Down), Ok(7));
+    }
+
+    #[test]
+    fn test_mul_div_zero_denominator() {
+        assert_eq!(
+            mul_div(1, 1, 0, Rounding::Down),
+            Err(SavingsError::InternalError)
+        );
+    }
+
+    #[test]
+    fn test_mul_div_at_i128_extremes() {
+        // The intermediate product overflows i128 but the result does not
+        assert_eq!(
+            mul_div(i128::MAX, 10_000, 10_000, Rounding::Down),
+            Ok(i128::MAX)
+        );
+        assert_eq!(
+            mul_div(i128::MAX, i128::MAX, i128::MAX, Rounding::Up),
+            Ok(i128::MAX)
+        );
+        assert_eq!(
+            mul_div(i128::MIN, 10_000, 10_000, Rounding::Down),
+            Ok(i128::MIN)
+        );
+        // floor((2^127 - 1) * 9_999 / 10_000)
+        assert_eq!(
+            mul_div(i128::MAX, 9_999, 10_000, Rounding::Down),
+            Ok(170_124_169_342_123_184_808_514_134_985_512_517_316)
+        );
+        assert_eq!(
+            mul_div(i128::MAX, 9_999, 10_000, Rounding::Up),
+            Ok(170_124_169_342_123_184_808_514_134_985_512_517_317)
+        );
+    }
+
+    #[test]
+    fn test_mul_div_overflowing_result() {
+        assert_eq!(
+            mul_div(i128::MAX, 2, 1, Rounding::Down),
+            Err(SavingsError::Overflow)
+        );
+        assert_eq!(
+            mul_div(i128::MIN, -1, 1, Rounding::Down),
+            Err(SavingsError::Overflow)
+        );
+        assert_eq!(
+            mul_div(i128::MAX, i128::MAX, 1, Rounding::Down),
+            Err(SavingsError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_apply_bps() {
+        assert_eq!(apply_bps(3_333, 125, Rounding::Down), Ok(41));
+        assert_eq!(apply_bps(3_333, 125, Rounding::Up), Ok(42));
+        assert_eq!(apply_bps(i128::MAX, 10_000, Rounding::Down), Ok(i128::MAX));
+        // 1 bps of the largest balance: floor((2^127 - 1) / 10_000)
+        assert_eq!(
+            apply_bps(i128::MAX, 1, Rounding::Down),
+            Ok(17_014_118_346_046_923_173_168_730_371_588_410)
+        );
+    }
+
+    #[test]
+    fn test_simple_interest() {
+        let year = SECONDS_PER_YEAR as u64;
+        assert_eq!(
+            simple_interest(1_000_000, 500, year, Rounding::Down),
+            Ok(50_000)
+        );
+        assert_eq!(
+            simple_interest(1_000_000, 500, year / 2, Rounding::Down),
+            Ok(25_000)
+        );
+        // One second of 5% on 1,000 is a tiny fraction of a unit
+        assert_eq!(simple_interest(1_000, 500, 1, Rounding::Down), Ok(0));
+        assert_eq!(simple_interest(1_000, 500, 1, Rounding::Up), Ok(1));
+    }
+
+    #[test]
+    fn test_simple_interest_on_extreme_balances() {
+        let year = SECONDS_PER_YEAR as u64;
+        // 5% of the largest balance over a year still fits in i128
+        let expected = apply_bps(i128::MAX, 500, Rounding::Down).unwrap();
+        assert_eq!(
+            simple_interest(i128::MAX, 500, year, Rounding::Down),
+            Ok(expected)
+        );
+        // 200% over a year would exceed i128
+        assert_eq!(
+            simple_interest(i128::MAX, 20_000, year, Rounding::Down),
+            Err(SavingsError::Overflow)
+        );
+    }
+}
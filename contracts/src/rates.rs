@@ -1,7 +1,10 @@
 use crate::governance;
+use crate::math::{self, Rounding};
 use crate::storage_types::DataKey;
 use crate::SavingsError;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
+
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
 
 // --- Admin Setters (with governance transition) ---
 
@@ -47,12 +50,36 @@ pub fn set_lock_rate(
     caller.require_auth();
     governance::validate_admin_or_governance(env, &caller)?;
 
+    store_lock_rate(env, duration_days, rate)
+}
+
+/// Writes a `LockRate` tier and records its duration in the tier index.
+/// Shared by the admin setter and governance execution.
+pub(crate) fn store_lock_rate(
+    env: &Env,
+    duration_days: u64,
+    rate: i128,
+) -> Result<(), SavingsError> {
     if rate < 0 {
         return Err(SavingsError::InvalidInterestRate);
     }
     env.storage()
         .instance()
         .set(&DataKey::LockRate(duration_days), &rate);
+
+    // Keep the tier index sorted so lookups can stop at the first tier that
+    // is longer than the requested duration
+    let mut tiers = get_lock_rate_tiers(env);
+    if !tiers.contains(duration_days) {
+        let position = tiers
+            .iter()
+            .position(|d| d > duration_days)
+            .unwrap_or(tiers.len() as usize);
+        tiers.insert(position as u32, duration_days);
+        env.storage()
+            .instance()
+            .set(&DataKey::LockRateTiers, &tiers);
+    }
     Ok(())
 }
 
@@ -86,48 +113,56 @@ pub fn get_lock_rate(env: &Env, duration_days: u64) -> Result<i128, SavingsError
         .ok_or(SavingsError::PlanNotFound)
 }
 
-// --- Interest Calculation Helpers ---
+/// Returns the configured lock durations (in days), shortest first.
+pub fn get_lock_rate_tiers(env: &Env) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&DataKey::LockRateTiers)
+        .unwrap_or(Vec::new(env))
+}
 
-pub fn calculate_flexi_interest(balance: i128, rate: i128, duration_seconds: u64) -> i128 {
-    if balance <= 0 || rate <= 0 {
-        return 0;
+/// Prices a lock of `duration_seconds` from the `LockRate` table.
+///
+/// The best tier is the longest configured duration that does not exceed
+/// the requested one.
+///
+/// # Errors
+/// * `InvalidPlanConfig` - If no tier is at or below the requested duration
+pub fn get_lock_rate_for_duration(env: &Env, duration_seconds: u64) -> Result<i128, SavingsError> {
+    let duration_days = duration_seconds / SECONDS_PER_DAY;
+
+    let mut best_tier: Option<u64> = None;
+    for tier in get_lock_rate_tiers(env).iter() {
+        if tier > duration_days {
+            break;
+        }
+        best_tier = Some(tier);
     }
-    // Simple Interest: (balance * rate * time) / (10000 * 365 * 24 * 60 * 60)
-    // Assuming rate is in basis points (e.g. 500 = 5.00%)
-    // 10000 is for basis points
-    // Time is in seconds, so we divide by seconds in a year (~31536000)
-
-    // Using i128 for precision and to match input types
-    let numerator = balance
-        .checked_mul(rate)
-        .unwrap_or(0)
-        .checked_mul(duration_seconds as i128)
-        .unwrap_or(0);
 
-    let denominator = 10000i128 * 365 * 24 * 60 * 60;
+    let tier = best_tier.ok_or(SavingsError::InvalidPlanConfig)?;
+    get_lock_rate(env, tier)
+}
+
+// --- Interest Calculation Helpers ---
 
-    numerator / denominator
+/// Simple interest accrued on a Flexi balance over `duration_seconds`.
+///
+/// Interest rounds down, so any fraction of a unit stays with the protocol.
+pub fn calculate_flexi_interest(
+    balance: i128,
+    rate: i128,
+    duration_seconds: u64,
+) -> Result<i128, SavingsError> {
+    if balance <= 0 || rate <= 0 {
+        return Ok(0);
+    }
+    math::simple_interest(balance, rate, duration_seconds, Rounding::Down)
 }
 
-pub fn calculate_lock_interest(amount: i128, rate: i128) -> i128 {
+/// Flat return of `rate` basis points on `amount`, rounded down.
+pub fn calculate_lock_interest(amount: i128, rate: i128) -> Result<i128, SavingsError> {
     if amount <= 0 || rate <= 0 {
-        return 0;
+        return Ok(0);
     }
-    // For fixed lock, maybe it's (amount * rate) / 10000?
-    // Or is it annualized?
-    // "LockRate maps duration -> interest rate" usually implies a fixed yield for that duration or annualized.
-    // If it's APY, we need duration.
-    // However, if the rate is SPECIFIC to that lock duration tier (e.g. "5% for 30 days"), then it might be flat.
-    // BUT usually rates are APY.
-    // Let's assume simplest interpretation:
-    // The instructions say "LockRate => maps lock duration to interest rate".
-    // If I lock for 30 days, I get X rate.
-    // Let's assume the rate provided IS the return for that period for now, OR APY.
-    // Given 'calculate_flexi' has duration, but 'calculate_lock_interest' signature in prompt was:
-    // `calculate_lock_interest(amount: i128, rate: i128) -> i128`
-    // It DOES NOT take duration. This implies the 'rate' passed in is already the *effective* rate for the period check,
-    // OR it calculates a flat amount based on the rate (like a fixed % return).
-    // Let's go with: Result = (Amount * Rate) / 10000.
-
-    amount.checked_mul(rate).unwrap_or(0) / 10000
+    math::apply_bps(amount, rate, Rounding::Down)
 }
@@ -87,13 +87,14 @@ fn test_calculate_flexi_interest_logic() {
     let rate = 500; // 5%
     let duration = 365 * 24 * 60 * 60; // 1 year
 
-    let interest = crate::rates::calculate_flexi_interest(balance, rate, duration);
+    let interest = crate::rates::calculate_flexi_interest(balance, rate, duration).unwrap();
     // 1M * 5% = 50,000
     // Logic: (1M * 500 * duration) / (10000 * duration) = 50,000
     assert_eq!(interest, 50_000);
 
     // Half year
-    let interest_half = crate::rates::calculate_flexi_interest(balance, rate, duration / 2);
+    let interest_half =
+        crate::rates::calculate_flexi_interest(balance, rate, duration / 2).unwrap();
     assert_eq!(interest_half, 25_000);
 }
 
@@ -102,7 +103,7 @@ fn test_calculate_lock_interest_logic() {
     let amount = 1_000_000;
     let rate = 500; // 5% flat return?
 
-    let interest = crate::rates::calculate_lock_interest(amount, rate);
+    let interest = crate::rates::calculate_lock_interest(amount, rate).unwrap();
     assert_eq!(interest, 50_000);
 }
 
@@ -115,3 +116,80 @@ fn test_invalid_rates() {
     let res = client.try_set_flexi_rate(&admin, &-100);
     assert_eq!(res.unwrap_err(), Ok(SavingsError::InvalidInterestRate));
 }
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Registers a settlement asset and returns a funded, initialized user
+fn setup_lock_user(env: &Env, client: &NesteraContractClient, admin: &Address) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.set_settlement_asset(admin, &token);
+
+    let user = Address::generate(env);
+    client.initialize_user(&user);
+    soroban_sdk::token::StellarAssetClient::new(env, &token).mint(&user, &100_000);
+    user
+}
+
+#[test]
+fn test_lock_rate_tiers_are_kept_sorted() {
+    let (_env, client, admin) = setup();
+
+    client.set_lock_rate(&admin, &90, &900);
+    client.set_lock_rate(&admin, &30, &600);
+    client.set_lock_rate(&admin, &180, &1_200);
+    client.set_lock_rate(&admin, &30, &650); // update, not a new tier
+
+    let tiers = client.get_lock_rate_tiers();
+    assert_eq!(tiers.len(), 3);
+    assert_eq!(tiers.get(0), Some(30));
+    assert_eq!(tiers.get(1), Some(90));
+    assert_eq!(tiers.get(2), Some(180));
+    assert_eq!(client.get_lock_rate(&30), 650);
+}
+
+#[test]
+fn test_lock_rate_uses_best_tier_at_or_below_duration() {
+    let (_env, client, admin) = setup();
+
+    client.set_lock_rate(&admin, &30, &600);
+    client.set_lock_rate(&admin, &90, &900);
+
+    assert_eq!(client.get_lock_rate_for_duration(&(30 * DAY)), 600);
+    assert_eq!(client.get_lock_rate_for_duration(&(89 * DAY)), 600);
+    assert_eq!(client.get_lock_rate_for_duration(&(90 * DAY)), 900);
+    assert_eq!(client.get_lock_rate_for_duration(&(400 * DAY)), 900);
+}
+
+#[test]
+fn test_lock_save_without_matching_tier_is_rejected() {
+    let (env, client, admin) = setup();
+    let user = setup_lock_user(&env, &client, &admin);
+
+    client.set_lock_rate(&admin, &30, &600);
+
+    let res = client.try_get_lock_rate_for_duration(&(29 * DAY));
+    assert_eq!(res.unwrap_err(), Ok(SavingsError::InvalidPlanConfig));
+    assert!(client
+        .try_create_lock_save(&user, &1_000, &(29 * DAY))
+        .is_err());
+}
+
+#[test]
+fn test_lock_save_keeps_rate_it_was_created_with() {
+    let (env, client, admin) = setup();
+    let user = setup_lock_user(&env, &client, &admin);
+
+    client.set_lock_rate(&admin, &30, &600);
+    client.set_lock_rate(&admin, &90, &900);
+
+    let short_lock = client.create_lock_save(&user, &1_000, &(60 * DAY));
+    let long_lock = client.create_lock_save(&user, &1_000, &(120 * DAY));
+
+    // Repricing the table does not touch existing locks
+    client.set_lock_rate(&admin, &30, &100);
+
+    assert_eq!(client.get_lock_save_detail(&short_lock).interest_rate, 600);
+    assert_eq!(client.get_lock_save_detail(&long_lock).interest_rate, 900);
+}
@@ -0,0 +1,458 @@
+use crate::assets;
+use crate::errors::SavingsError;
+use crate::group;
+use crate::ledger::{self, Liability};
+use crate::storage_types::{DataKey, GroupDataKey, GroupSave, RoscaState, SavingsPlan};
+use crate::ttl;
+use soroban_sdk::{symbol_short, Address, Env, String, Vec};
+
+/// Creates a rotating savings (ROSCA) group.
+///
+/// Members join as with any group save. Once the creator starts the
+/// rotation, membership is frozen, every member contributes exactly
+/// `contribution_amount` per cycle of `cycle_seconds`, and each cycle's pot
+/// is paid to the next member in the payout order. ROSCA groups earn no
+/// interest, since the pot never stays in custody beyond a cycle.
+///
+/// # Arguments
+/// * `payout_order` - The members in the order they receive the pot, which
+///   must include the creator; `None` randomises the order when the
+///   rotation starts
+///
+/// # Errors
+/// * `InvalidAmount` - If contribution_amount <= 0
+/// * `InvalidTimestamp` - If cycle_seconds is 0
+/// * `InvalidGroupConfig` - If the payout order has fewer than two members,
+///   repeats a member or leaves out the creator
+// Each argument is a separate contract parameter of the entrypoint
+#[allow(clippy::too_many_arguments)]
+pub fn create_rosca_group(
+    env: &Env,
+    creator: Address,
+    asset: Address,
+    title: String,
+    description: String,
+    category: String,
+    contribution_amount: i128,
+    cycle_seconds: u64,
+    is_public: bool,
+    payout_order: Option<Vec<Address>>,
+) -> Result<u64, SavingsError> {
+    creator.require_auth();
+
+    if cycle_seconds == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+    let payout_order = payout_order.unwrap_or(Vec::new(env));
+    if !payout_order.is_empty() {
+        validate_order(&payout_order)?;
+        if !payout_order.contains(&creator) {
+            return Err(SavingsError::InvalidGroupConfig);
+        }
+    }
+
+    // Target and end time are placeholders until the rotation starts and
+    // the number of members is known
+    let now = env.ledger().timestamp();
+    let end_time = now
+        .checked_add(cycle_seconds)
+        .ok_or(SavingsError::Overflow)?;
+    let group_id = group::create_group_save(
+        env,
+        creator.clone(),
+        asset,
+        title,
+        description,
+        category,
+        contribution_amount,
+        0,
+        contribution_amount,
+        is_public,
+        now,
+        end_time,
+    )?;
+
+    let mut group = load_group(env, group_id)?;
+    group.interest_rate = 0;
+    group.period_seconds = cycle_seconds;
+    save_group(env, &group);
+
+    save_state(
+        env,
+        group_id,
+        &RoscaState {
+            cycle_seconds,
+            payout_order,
+            started_at: None,
+            current_cycle: 0,
+            contributed: Vec::new(env),
+        },
+    );
+
+    env.events()
+        .publish((symbol_short!("rosca_new"), creator), group_id);
+
+    Ok(group_id)
+}
+
+/// Starts the rotation of a ROSCA group, opening its first cycle now.
+///
+/// A fixed payout order must list exactly the current members; otherwise the
+/// members are shuffled with the ledger-seeded PRNG.
+///
+/// # Returns
+/// The payout order
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group is not a ROSCA, has already started,
+///   has fewer than two members, or they do not match the fixed payout order
+pub fn start_rosca(
+    env: &Env,
+    creator: Address,
+    group_id: u64,
+) -> Result<Vec<Address>, SavingsError> {
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    let mut state = get_rosca_state(env, group_id).ok_or(SavingsError::InvalidGroupConfig)?;
+    if state.started_at.is_some() {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let mut members = group::get_group_members(env, group_id);
+    if members.len() < 2 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    if state.payout_order.is_empty() {
+        env.prng().shuffle(&mut members);
+        state.payout_order = members;
+    } else if state.payout_order.len() != members.len()
+        || members.iter().any(|m| !state.payout_order.contains(&m))
+    {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let cycles = state.payout_order.len();
+    let now = env.ledger().timestamp();
+    group.start_time = now;
+    group.end_time = state
+        .cycle_seconds
+        .checked_mul(cycles as u64)
+        .and_then(|length| now.checked_add(length))
+        .ok_or(SavingsError::Overflow)?;
+    group.target_amount = pot_size(&group, cycles)?;
+    state.started_at = Some(now);
+
+    save_group(env, &group);
+    save_state(env, group_id, &state);
+
+    env.events().publish(
+        (symbol_short!("rosca_go"), group_id),
+        state.payout_order.clone(),
+    );
+
+    Ok(state.payout_order)
+}
+
+/// Pays the current cycle's pot to the next member in the payout order and
+/// moves the group on to the next cycle. Anyone may call this.
+///
+/// The group completes once every member has received the pot. If a member
+/// misses a cycle, the creator may cancel the rotation once the cycle's
+/// deadline passes.
+///
+/// # Returns
+/// The member who received the pot
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `InvalidGroupConfig` - If the group is not a ROSCA
+/// * `TooEarly` - If the rotation has not started
+/// * `PlanCompleted` - If every cycle has been paid out
+/// * `GroupCycleIncomplete` - If a member has not contributed to the cycle
+pub fn payout_rosca_cycle(env: &Env, group_id: u64) -> Result<Address, SavingsError> {
+    let mut group = load_group(env, group_id)?;
+    let mut state = get_rosca_state(env, group_id).ok_or(SavingsError::InvalidGroupConfig)?;
+    if state.started_at.is_none() {
+        return Err(SavingsError::TooEarly);
+    }
+    if group.is_completed {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    let order = state.payout_order.clone();
+    if state.contributed.len() < order.len() {
+        return Err(SavingsError::GroupCycleIncomplete);
+    }
+
+    let cycle = state.current_cycle;
+    let recipient = order.get(cycle).ok_or(SavingsError::InternalError)?;
+    let pot = pot_size(&group, order.len())?;
+
+    state.current_cycle += 1;
+    state.contributed = Vec::new(env);
+    group.current_amount = group
+        .current_amount
+        .checked_sub(pot)
+        .ok_or(SavingsError::Underflow)?;
+    if state.current_cycle == order.len() {
+        group.is_completed = true;
+    }
+
+    // Each member's stake in the pot leaves with it
+    for member in order.iter() {
+        release_member_stake(env, &group, &member, group.contribution_amount)?;
+    }
+
+    save_group(env, &group);
+    save_state(env, group_id, &state);
+
+    ledger::adjust(env, Liability::Group, &group.asset, -pot)?;
+    assets::pay(env, &group.asset, &recipient, pot);
+
+    env.events().publish(
+        (symbol_short!("rosca_pay"), group_id, cycle),
+        (recipient.clone(), pot),
+    );
+
+    Ok(recipient)
+}
+
+/// Cancels a ROSCA rotation whose current cycle was not fully paid in by its
+/// deadline, one `cycle_seconds` after the cycle opened.
+///
+/// Members who contributed to the unpaid cycle get their contribution back
+/// and the group is closed. Pots paid out in earlier cycles are unaffected.
+///
+/// # Returns
+/// The total refunded
+///
+/// # Errors
+/// * `PlanNotFound` - If the group does not exist
+/// * `Unauthorized` - If the caller is not the group creator
+/// * `InvalidGroupConfig` - If the group is not a ROSCA
+/// * `TooEarly` - If the rotation has not started or the cycle deadline has
+///   not passed
+/// * `PlanCompleted` - If every cycle has been paid out
+/// * `InvalidGroupConfig` - If every member has paid into the cycle, so its
+///   pot can still be paid out
+pub fn cancel_rosca(env: &Env, creator: Address, group_id: u64) -> Result<i128, SavingsError> {
+    creator.require_auth();
+
+    let mut group = load_group(env, group_id)?;
+    if group.creator != creator {
+        return Err(SavingsError::Unauthorized);
+    }
+    let mut state = get_rosca_state(env, group_id).ok_or(SavingsError::InvalidGroupConfig)?;
+    if group.is_completed {
+        return Err(SavingsError::PlanCompleted);
+    }
+    let deadline = cycle_opens(&state)?
+        .checked_add(state.cycle_seconds)
+        .ok_or(SavingsError::Overflow)?;
+    if env.ledger().timestamp() < deadline {
+        return Err(SavingsError::TooEarly);
+    }
+    if state.contributed.len() >= state.payout_order.len() {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+
+    let refunded = group
+        .contribution_amount
+        .checked_mul(state.contributed.len() as i128)
+        .ok_or(SavingsError::Overflow)?;
+    group.current_amount = group
+        .current_amount
+        .checked_sub(refunded)
+        .ok_or(SavingsError::Underflow)?;
+    group.is_completed = true;
+
+    for member in state.payout_order.iter() {
+        let stake = if state.contributed.contains(&member) {
+            group.contribution_amount
+        } else {
+            0
+        };
+        release_member_stake(env, &group, &member, stake)?;
+    }
+    let contributed = state.contributed.clone();
+    state.contributed = Vec::new(env);
+
+    save_group(env, &group);
+    save_state(env, group_id, &state);
+
+    ledger::adjust(env, Liability::Group, &group.asset, -refunded)?;
+    for member in contributed.iter() {
+        assets::pay(env, &group.asset, &member, group.contribution_amount);
+    }
+
+    env.events().publish(
+        (symbol_short!("rosca_end"), group_id, state.current_cycle),
+        refunded,
+    );
+
+    Ok(refunded)
+}
+
+/// Returns a group's rotation state, or `None` if it is not a ROSCA group.
+pub fn get_rosca_state(env: &Env, group_id: u64) -> Option<RoscaState> {
+    env.storage()
+        .persistent()
+        .get(&GroupDataKey::Rosca(group_id))
+}
+
+/// Records a member's contribution to the current cycle of a ROSCA group.
+///
+/// # Returns
+/// `false` if the group is not a ROSCA, so the contribution follows the
+/// ordinary group rules
+///
+/// # Errors
+/// * `PlanCompleted` - If every cycle has been paid out
+/// * `TooEarly` - If the rotation has not started or the current cycle has
+///   not opened yet
+/// * `InvalidAmount` - If the amount is not the group's contribution amount
+/// * `AmountExceedsLimit` - If the member has already contributed this cycle
+pub(crate) fn record_contribution(
+    env: &Env,
+    group: &GroupSave,
+    user: &Address,
+    amount: i128,
+) -> Result<bool, SavingsError> {
+    let Some(mut state) = get_rosca_state(env, group.id) else {
+        return Ok(false);
+    };
+    if group.is_completed {
+        return Err(SavingsError::PlanCompleted);
+    }
+
+    if env.ledger().timestamp() < cycle_opens(&state)? {
+        return Err(SavingsError::TooEarly);
+    }
+
+    if amount != group.contribution_amount {
+        return Err(SavingsError::InvalidAmount);
+    }
+    if state.contributed.contains(user) {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+
+    state.contributed.push_back(user.clone());
+    save_state(env, group.id, &state);
+    Ok(true)
+}
+
+/// Membership of a ROSCA group is frozen once the rotation starts, and a
+/// fixed payout order limits who may join before then.
+///
+/// # Errors
+/// * `InvalidGroupConfig` - If `user` may not join or leave the group now
+pub(crate) fn check_membership_change(
+    env: &Env,
+    group_id: u64,
+    user: &Address,
+) -> Result<(), SavingsError> {
+    let Some(state) = get_rosca_state(env, group_id) else {
+        return Ok(());
+    };
+    if state.started_at.is_some() {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    if !state.payout_order.is_empty() && !state.payout_order.contains(user) {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    Ok(())
+}
+
+fn validate_order(order: &Vec<Address>) -> Result<(), SavingsError> {
+    if order.len() < 2 {
+        return Err(SavingsError::InvalidGroupConfig);
+    }
+    for i in 0..order.len() {
+        if order.first_index_of(order.get(i).unwrap()) != Some(i) {
+            return Err(SavingsError::InvalidGroupConfig);
+        }
+    }
+    Ok(())
+}
+
+/// Ledger timestamp the current cycle opened
+///
+/// # Errors
+/// * `TooEarly` - If the rotation has not started
+fn cycle_opens(state: &RoscaState) -> Result<u64, SavingsError> {
+    let started_at = state.started_at.ok_or(SavingsError::TooEarly)?;
+    state
+        .cycle_seconds
+        .checked_mul(state.current_cycle as u64)
+        .and_then(|offset| started_at.checked_add(offset))
+        .ok_or(SavingsError::Overflow)
+}
+
+fn pot_size(group: &GroupSave, members: u32) -> Result<i128, SavingsError> {
+    group
+        .contribution_amount
+        .checked_mul(members as i128)
+        .ok_or(SavingsError::Overflow)
+}
+
+/// Takes a cycle's contribution of `stake` off a member's recorded balance
+/// once it has been paid out or refunded.
+fn release_member_stake(
+    env: &Env,
+    group: &GroupSave,
+    member: &Address,
+    stake: i128,
+) -> Result<(), SavingsError> {
+    let contribution_key = DataKey::GroupMemberContribution(group.id, member.clone());
+    let contribution: i128 = env
+        .storage()
+        .persistent()
+        .get(&contribution_key)
+        .unwrap_or(0);
+    let remaining = contribution
+        .checked_sub(stake)
+        .ok_or(SavingsError::Underflow)?;
+    env.storage()
+        .persistent()
+        .set(&contribution_key, &remaining);
+
+    let plan_key = DataKey::SavingsPlan(member.clone(), group.id);
+    if let Some(mut plan) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, SavingsPlan>(&plan_key)
+    {
+        plan.balance = remaining;
+        plan.is_completed = group.is_completed;
+        env.storage().persistent().set(&plan_key, &plan);
+    }
+    Ok(())
+}
+
+fn load_group(env: &Env, group_id: u64) -> Result<GroupSave, SavingsError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GroupSave(group_id))
+        .ok_or(SavingsError::PlanNotFound)
+}
+
+fn save_group(env: &Env, group: &GroupSave) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::GroupSave(group.id), group);
+    ttl::extend_group_ttl(env, group.id);
+}
+
+fn save_state(env: &Env, group_id: u64, state: &RoscaState) {
+    let key = GroupDataKey::Rosca(group_id);
+    env.storage().persistent().set(&key, state);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl::LOW_THRESHOLD, ttl::EXTEND_TO);
+}
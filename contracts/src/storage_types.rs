@@ -1,4 +1,4 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Symbol};
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, Vec};
 
 /// Represents the different types of savings plans available in Nestera
 #[contracttype]
@@ -52,6 +52,7 @@ pub struct GroupSave {
     pub title: String,
     pub description: String,
     pub category: String,
+    pub asset: Address,
     pub target_amount: i128,
     pub current_amount: i128,
     pub contribution_type: u32,
@@ -61,6 +62,36 @@ pub struct GroupSave {
     pub start_time: u64,
     pub end_time: u64,
     pub is_completed: bool,
+    /// GroupRate in force when the group was created
+    pub interest_rate: u32,
+    /// Contribution-seconds accumulated by the pool up to `last_accrual`
+    pub total_weight: i128,
+    /// Ledger timestamp of the last weight update; frozen at completion
+    pub last_accrual: u64,
+    /// Length of a contribution period for fixed and percentage groups,
+    /// counted from `start_time`; defaults to the whole group
+    pub period_seconds: u64,
+    /// Most members the group admits; 0 for no limit
+    pub max_members: u32,
+}
+
+/// Rotation state of a ROSCA (rotating savings) group: each cycle every
+/// member contributes `contribution_amount` and the whole pot is paid to the
+/// next member in `payout_order`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoscaState {
+    /// Length of each contribution cycle in seconds
+    pub cycle_seconds: u64,
+    /// Members in the order they receive the pot; fixed by the creator, or
+    /// empty until the rotation starts if it is to be randomised
+    pub payout_order: Vec<Address>,
+    /// Ledger timestamp the first cycle opened, once the rotation starts
+    pub started_at: Option<u64>,
+    /// Zero-based index of the cycle collecting contributions
+    pub current_cycle: u32,
+    /// Members who have contributed to the current cycle
+    pub contributed: Vec<Address>,
 }
 
 /// Represents a Lock Save plan with fixed duration and maturity
@@ -69,11 +100,18 @@ pub struct GroupSave {
 pub struct LockSave {
     pub id: u64,
     pub owner: Address,
+    pub asset: Address,
     pub amount: i128,
     pub interest_rate: u32,
     pub start_time: u64,
     pub maturity_time: u64,
     pub is_withdrawn: bool,
+    /// Yield settled at earlier rates or principal sizes, paid at withdrawal
+    pub accrued_interest: i128,
+    /// When yield on the current principal and rate started accruing
+    pub last_accrual: u64,
+    /// Whether a keeper may re-lock this save for another term once it matures
+    pub auto_rollover: bool,
 }
 
 /// Custom error types for the savings contract
@@ -95,11 +133,22 @@ pub enum SavingsError {
 pub struct GoalSave {
     pub id: u64,
     pub owner: Address,
+    pub asset: Address,
     pub goal_name: Symbol,
     pub target_amount: i128,
     pub current_amount: i128,
     pub interest_rate: u32,
     pub start_time: u64,
+    /// Interest settled on `current_amount` up to `last_accrual`
+    pub accrued_interest: i128,
+    /// Ledger timestamp up to which `accrued_interest` has been settled
+    pub last_accrual: u64,
+    /// Optional target date for reaching `target_amount`
+    pub deadline: Option<u64>,
+    /// Highest progress milestone (25, 50, 75 or 100 percent) reached so far
+    pub last_milestone: u32,
+    /// Set when the goal first reached its target after the deadline
+    pub completed_late: bool,
     pub is_completed: bool,
     pub is_withdrawn: bool,
 }
@@ -113,7 +162,148 @@ pub struct AutoSave {
     pub amount: i128,
     pub interval_seconds: u64,
     pub next_execution_time: u64,
-    pub is_active: bool,
+    pub status: AutoSaveStatus,
+    pub destination: AutoSaveDestination,
+    /// Ledger sequence at which the user's token allowance to the contract
+    /// expires; executions pull funds against that allowance
+    pub allowance_expiry_ledger: u32,
+    /// Failed executions since the last success
+    pub consecutive_failures: u32,
+    /// `SavingsError` code of the most recent failed execution
+    pub last_failure_reason: Option<u32>,
+    /// Ledger timestamp of the most recent successful execution
+    pub last_success_time: Option<u64>,
+    /// How an execution handles intervals a keeper missed
+    pub missed_policy: MissedIntervalPolicy,
+}
+
+/// Lifecycle state of an AutoSave schedule; only `Active` schedules execute
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutoSaveStatus {
+    Active,
+    /// Paused by the owner until resumed
+    Paused,
+    /// Cancelled by the owner; final
+    Cancelled,
+    /// Stopped by the contract after its destination closed or it failed
+    /// `AutoSaveMaxFailures` times in a row; the owner may resume it
+    Deactivated,
+}
+
+/// Storage keys for group save extensions, kept apart from `DataKey`, which
+/// is at the variant limit for a contract type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GroupDataKey {
+    /// Maps a ROSCA group to its RoscaState
+    Rosca(u64),
+    /// Addresses with an outstanding invitation to a group
+    Invites(u64),
+    /// SHA-256 hashes of a group's unredeemed invite codes
+    InviteCodes(u64),
+    /// Marks a public group whose joins need the creator's approval
+    RequiresApproval(u64),
+    /// Users waiting for the creator to approve their join request
+    JoinRequests(u64),
+    /// Last period a member paid into a fixed-contribution group
+    LastPeriod(u64, Address),
+    /// Per-member share of a percentage group for a contribution period
+    PeriodShare(u64, u64),
+}
+
+/// How an AutoSave execution handles intervals that passed without a run
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MissedIntervalPolicy {
+    /// Run one interval and advance by one, leaving any backlog due
+    Single,
+    /// Run one interval and jump to the next slot after now
+    SkipMissed,
+    /// Deposit every missed interval in one run, up to `max_periods`
+    CatchUp(u32),
+}
+
+/// Storage keys for the per-recipient fee ledger, kept apart from `DataKey`,
+/// which is at the variant limit for a contract type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeDataKey {
+    /// Maps (fee recipient, asset) to the ProtocolFeeBalance credited to them
+    RecipientFees(Address, Address),
+    /// Maps an asset to its unswept fees as FeeCredit lots, oldest first
+    Credits(Address),
+}
+
+/// Unswept fees in one asset booked while `recipient` was the fee recipient,
+/// or while none was configured
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeCredit {
+    pub recipient: Option<Address>,
+    pub amount: i128,
+}
+
+/// Storage keys for AutoSave settings, kept apart from `DataKey`, which is
+/// at the variant limit for a contract type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AutoSaveDataKey {
+    /// Consecutive failed executions after which a schedule is deactivated
+    MaxFailures,
+    /// The KeeperTipConfig paid to registered keepers
+    KeeperTip,
+    /// Marks an address as a registered keeper
+    Keeper(Address),
+    /// Maps a keeper to the KeeperEarning totals it has been paid
+    KeeperEarnings(Address),
+    /// Maps an hour bucket of `next_execution_time` to the IDs of the
+    /// active schedules due in it
+    DueBucket(u64),
+    /// Sorted list of the non-empty due buckets
+    DueBuckets,
+}
+
+/// How a keeper is tipped for each AutoSave schedule it executes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeeperTip {
+    Disabled,
+    /// Basis points of each executed amount
+    Bps(u32),
+    /// A fixed amount per executed schedule, in the schedule's asset
+    Flat(i128),
+}
+
+/// Keeper tip settings, funded from accrued protocol fees
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperTipConfig {
+    pub tip: KeeperTip,
+    /// Most a keeper is paid per asset in one `execute_due_autosaves` batch
+    pub max_per_batch: i128,
+}
+
+/// Total tips a keeper has been paid in one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperEarning {
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Where each AutoSave execution deposits its amount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AutoSaveDestination {
+    /// Flexi balance in the settlement asset
+    Flexi,
+    /// Deposit into an existing Goal Save (goal ID)
+    Goal(u64),
+    /// Contribution to a Group Save the user belongs to (group ID)
+    Group(u64),
+    /// A new Lock Save of the given duration in seconds on every run
+    Lock(u64),
 }
 
 /// Storage keys for the contract's persistent data
@@ -139,13 +329,41 @@ pub enum DataKey {
     PlatformFee,
     /// Early break fee (basis points) for goal saves
     EarlyBreakFeeBps,
+    /// Penalty in bps for breaking a lock on day one, scaled down linearly
+    /// with the share of the lock duration already served
+    LockBreakPenaltyBps,
     /// Fee recipient for protocol/treasury fees
     FeeRecipient,
+    /// Stellar Asset Contract that savings are settled in
+    SettlementAsset,
+    /// Allowlist of asset contracts accepted for deposits
+    SupportedAssets,
     User(Address),
     /// Maps a (user address, plan_id) tuple to a SavingsPlan
     SavingsPlan(Address, u64),
-    FlexiBalance(Address),
+    /// Maps (user, asset) to the user's Flexi balance in that asset
+    FlexiBalance(Address, Address),
+    /// Maps user to the list of assets they have held Flexi balances in
+    UserFlexiAssets(Address),
+    /// Maps (user, asset) to the FlexiInterest accrued on that balance
+    FlexiInterest(Address, Address),
     TotalBalance(Address),
+    /// Maps an asset to its ProtocolFeeBalance ledger
+    AssetFees(Address),
+    /// Maps an asset to the total of all Flexi balances held in it
+    TotalFlexi(Address),
+    /// Maps an asset to the total principal of open Lock saves in it
+    TotalLocked(Address),
+    /// Maps an asset to the total held in open Goal saves in it
+    TotalGoal(Address),
+    /// Maps an asset to the total of all group contributions in it
+    TotalGroup(Address),
+    /// Maps an asset to interest settled onto user positions but not yet paid
+    TotalInterest(Address),
+    /// List of every asset the solvency ledger has recorded a balance in
+    LedgerAssets,
+    /// Maps an asset to the StrategyConfig that invests its idle liquidity
+    Strategy(Address),
     /// Maps group ID to GroupSave struct
     GroupSave(u64),
     /// Maps user address to list of GroupSave IDs they participate in
@@ -168,6 +386,8 @@ pub enum DataKey {
     GroupMemberContribution(u64, Address),
     /// Maps group_id to list of member addresses
     GroupMembers(u64),
+    /// Maps (group_id, user) to their GroupMemberAccrual
+    GroupMemberAccrual(u64, Address),
     /// Maps schedule ID to AutoSave struct
     AutoSave(u64),
     /// Maps user to a list of their AutoSave schedule IDs
@@ -180,6 +400,97 @@ pub enum DataKey {
     GroupRate,
     /// Maps duration (days) to interest rate
     LockRate(u64),
+    /// Sorted list of durations (days) that have a LockRate configured
+    LockRateTiers,
+}
+
+/// Interest earned on a Flexi balance that has not yet been compounded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlexiInterest {
+    pub pending: i128,
+    /// Ledger timestamp up to which `pending` has been accrued
+    pub last_accrual: u64,
+}
+
+/// A member's time-weighted stake in a group's interest
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupMemberAccrual {
+    /// Contribution-seconds accumulated up to `last_accrual`
+    pub weight: i128,
+    pub last_accrual: u64,
+    /// Whether the member's interest share has been paid out
+    pub claimed: bool,
+}
+
+/// A yield strategy registered for an asset and the amount allocated to it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyConfig {
+    pub strategy: Address,
+    /// Most of the Flexi/Lock liquidity that may be allocated, in bps
+    pub max_allocation_bps: u32,
+    /// Share of the Flexi/Lock liquidity that must stay in the contract, in bps
+    pub buffer_bps: u32,
+    /// Principal currently deposited with the strategy
+    pub allocated: i128,
+}
+
+/// Reported position of an asset's yield strategy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrategyAllocation {
+    pub strategy: Address,
+    pub max_allocation_bps: u32,
+    pub buffer_bps: u32,
+    pub allocated: i128,
+    /// Value the strategy reports holding, including any yield
+    pub total_assets: i128,
+    /// Flexi/Lock principal the allocation cap is measured against
+    pub liquidity: i128,
+    /// Tokens held by the contract itself
+    pub liquid_balance: i128,
+}
+
+/// What the contract owes in an asset, by category, against what it holds
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolvencyReport {
+    pub asset: Address,
+    pub flexi: i128,
+    pub locked: i128,
+    pub goal: i128,
+    pub group: i128,
+    /// Protocol fees not yet swept to the treasury
+    pub fees: i128,
+    /// Interest settled onto user positions but not yet paid out
+    pub interest: i128,
+    /// Sum of every category above
+    pub liabilities: i128,
+    /// Tokens in custody plus principal allocated to the yield strategy
+    pub holdings: i128,
+}
+
+/// A user's savings in a single asset, broken down by plan type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetHolding {
+    pub asset: Address,
+    pub flexi: i128,
+    pub lock: i128,
+    pub goal: i128,
+    pub group: i128,
+    pub total: i128,
+}
+
+/// Protocol fees still held in custody (`accrued`) and already paid out to
+/// the treasury (`swept`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFeeBalance {
+    pub accrued: i128,
+    pub swept: i128,
 }
 
 /// Payload structure that the admin signs off-chain
@@ -217,6 +528,11 @@ pub struct GoalSaveView {
     pub target_amount: i128,
     pub start_time: u64,
     pub interest_rate: u32,
+    /// Interest earned so far, paid out on completed withdrawal
+    pub accrued_interest: i128,
+    pub deadline: Option<u64>,
+    /// True if the deadline passed before the target was reached
+    pub missed_deadline: bool,
     pub is_completed: bool,
     pub contribution_type: u32,
     pub goal_name: Symbol,
@@ -0,0 +1,419 @@
+use crate::assets;
+use crate::config;
+use crate::errors::SavingsError;
+use crate::ledger;
+use crate::math::{self, Rounding};
+use crate::storage_types::{DataKey, StrategyAllocation, StrategyConfig};
+use crate::ttl;
+use soroban_sdk::{contractclient, symbol_short, token, Address, Env};
+
+/// Interface a yield strategy contract must implement.
+///
+/// A strategy holds a single asset on behalf of this contract. Tokens are
+/// transferred to the strategy before `deposit` is called, and `withdraw`
+/// must transfer the tokens back to `to` itself.
+#[contractclient(name = "YieldStrategyClient")]
+pub trait YieldStrategy {
+    /// Records `amount` tokens that have already been transferred in.
+    fn deposit(env: Env, amount: i128) -> i128;
+
+    /// Sends up to `amount` tokens to `to`, returning the amount sent.
+    fn withdraw(env: Env, to: Address, amount: i128) -> i128;
+
+    /// Total value held for this contract, including any yield earned.
+    fn total_assets(env: Env) -> i128;
+}
+
+/// Registers the strategy that idle liquidity in `asset` may be allocated to.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `admin` - The admin calling this function
+/// * `asset` - The asset the strategy invests
+/// * `strategy` - The strategy contract implementing `YieldStrategy`
+/// * `max_allocation_bps` - Cap on the share of Flexi/Lock liquidity allocated
+/// * `buffer_bps` - Share of Flexi/Lock liquidity kept liquid for withdrawals
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+/// * `SavingsError::InvalidPlanConfig` - If the bps values are out of range, or
+///   funds are still allocated to a different strategy
+pub fn set_strategy(
+    env: &Env,
+    admin: Address,
+    asset: Address,
+    strategy: Address,
+    max_allocation_bps: u32,
+    buffer_bps: u32,
+) -> Result<(), SavingsError> {
+    config::require_admin(env, &admin)?;
+    store_strategy(env, asset, strategy, max_allocation_bps, buffer_bps)
+}
+
+/// Moves `amount` of `asset` out of custody into its registered strategy.
+///
+/// # Errors
+/// * `SavingsError::Unauthorized` - If caller is not the admin
+/// * `SavingsError::AmountExceedsLimit` - If the allocation would exceed its cap
+/// * `SavingsError::InsufficientBalance` - If it would eat into the liquid buffer
+pub fn allocate_to_strategy(
+    env: &Env,
+    admin: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<(), SavingsError> {
+    config::require_admin(env, &admin)?;
+    allocate(env, &asset, amount)
+}
+
+/// Withdraws up to `amount` of `asset` from its strategy back into custody.
+///
+/// # Returns
+/// The amount the strategy actually returned
+pub fn recall_from_strategy(
+    env: &Env,
+    admin: Address,
+    asset: Address,
+    amount: i128,
+) -> Result<i128, SavingsError> {
+    config::require_admin(env, &admin)?;
+    recall(env, &asset, amount)
+}
+
+/// Validates and stores a strategy registration. Shared with governance.
+pub(crate) fn store_strategy(
+    env: &Env,
+    asset: Address,
+    strategy: Address,
+    max_allocation_bps: u32,
+    buffer_bps: u32,
+) -> Result<(), SavingsError> {
+    if max_allocation_bps as u64 + buffer_bps as u64 > 10_000 {
+        return Err(SavingsError::InvalidPlanConfig);
+    }
+    assets::require_supported_asset(env, &asset)?;
+
+    let allocated = match get_config(env, &asset) {
+        Some(existing) if existing.allocated > 0 && existing.strategy != strategy => {
+            return Err(SavingsError::InvalidPlanConfig);
+        }
+        Some(existing) => existing.allocated,
+        None => 0,
+    };
+
+    set_config(
+        env,
+        &asset,
+        &StrategyConfig {
+            strategy: strategy.clone(),
+            max_allocation_bps,
+            buffer_bps,
+            allocated,
+        },
+    );
+
+    env.events().publish(
+        (symbol_short!("strat_set"), asset),
+        (strategy, max_allocation_bps, buffer_bps),
+    );
+
+    Ok(())
+}
+
+/// Deposits `amount` of `asset` with its strategy within the cap and buffer.
+pub(crate) fn allocate(env: &Env, asset: &Address, amount: i128) -> Result<(), SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    let mut cfg = get_config(env, asset).ok_or(SavingsError::PlanNotFound)?;
+
+    let liquidity = ledger::get_flexi_lock_liquidity(env, asset)?;
+    let cap = math::apply_bps(liquidity, cfg.max_allocation_bps as i128, Rounding::Down)?;
+    let new_allocated = cfg
+        .allocated
+        .checked_add(amount)
+        .ok_or(SavingsError::Overflow)?;
+    if new_allocated > cap {
+        return Err(SavingsError::AmountExceedsLimit);
+    }
+
+    let buffer = math::apply_bps(liquidity, cfg.buffer_bps as i128, Rounding::Up)?;
+    let token = token::Client::new(env, asset);
+    let contract = env.current_contract_address();
+    if token.balance(&contract) - amount < buffer {
+        return Err(SavingsError::InsufficientBalance);
+    }
+
+    cfg.allocated = new_allocated;
+    set_config(env, asset, &cfg);
+
+    token.transfer(&contract, &cfg.strategy, &amount);
+    YieldStrategyClient::new(env, &cfg.strategy).deposit(&amount);
+
+    env.events()
+        .publish((symbol_short!("strat_in"), asset.clone()), amount);
+
+    Ok(())
+}
+
+/// Withdraws up to `amount` of `asset` from its strategy into custody.
+///
+/// Anything returned beyond the allocated principal is yield and simply
+/// stays in custody.
+pub(crate) fn recall(env: &Env, asset: &Address, amount: i128) -> Result<i128, SavingsError> {
+    if amount <= 0 {
+        return Err(SavingsError::InvalidAmount);
+    }
+    let mut cfg = get_config(env, asset).ok_or(SavingsError::PlanNotFound)?;
+
+    let received = YieldStrategyClient::new(env, &cfg.strategy)
+        .withdraw(&env.current_contract_address(), &amount);
+
+    cfg.allocated = (cfg.allocated - received).max(0);
+    set_config(env, asset, &cfg);
+
+    env.events()
+        .publish((symbol_short!("strat_out"), asset.clone()), received);
+
+    Ok(received)
+}
+
+/// Recalls enough of `asset` from its strategy, if one is registered, to
+/// cover a payout of `amount` that exceeds the tokens held in custody.
+pub(crate) fn ensure_liquid(env: &Env, asset: &Address, amount: i128) -> Result<(), SavingsError> {
+    let Some(cfg) = get_config(env, asset) else {
+        return Ok(());
+    };
+
+    let on_hand = token::Client::new(env, asset).balance(&env.current_contract_address());
+    let shortfall = amount - on_hand;
+    if shortfall <= 0 {
+        return Ok(());
+    }
+
+    let available = YieldStrategyClient::new(env, &cfg.strategy).total_assets();
+    let to_recall = shortfall.min(available);
+    if to_recall > 0 {
+        recall(env, asset, to_recall)?;
+    }
+    Ok(())
+}
+
+/// Reports the strategy position for `asset`.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If no strategy is registered for `asset`
+pub fn get_strategy_allocation(
+    env: &Env,
+    asset: Address,
+) -> Result<StrategyAllocation, SavingsError> {
+    let cfg = get_config(env, &asset).ok_or(SavingsError::PlanNotFound)?;
+
+    Ok(StrategyAllocation {
+        total_assets: YieldStrategyClient::new(env, &cfg.strategy).total_assets(),
+        liquidity: ledger::get_flexi_lock_liquidity(env, &asset)?,
+        liquid_balance: token::Client::new(env, &asset).balance(&env.current_contract_address()),
+        strategy: cfg.strategy,
+        max_allocation_bps: cfg.max_allocation_bps,
+        buffer_bps: cfg.buffer_bps,
+        allocated: cfg.allocated,
+    })
+}
+
+fn get_config(env: &Env, asset: &Address) -> Option<StrategyConfig> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Strategy(asset.clone()))
+}
+
+fn set_config(env: &Env, asset: &Address, cfg: &StrategyConfig) {
+    let key = DataKey::Strategy(asset.clone());
+    env.storage().persistent().set(&key, cfg);
+    ttl::extend_config_ttl(env, &key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::YieldStrategy;
+    use crate::{NesteraContract, NesteraContractClient, SavingsError};
+    use soroban_sdk::{
+        contract, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+        token::{StellarAssetClient, TokenClient},
+        Address, BytesN, Env,
+    };
+
+    /// Strategy that simply holds the tokens it is given
+    #[contract]
+    struct MockStrategy;
+
+    #[contractimpl]
+    impl MockStrategy {
+        pub fn init(env: Env, token: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("token"), &token);
+        }
+    }
+
+    fn mock_token(env: &Env) -> TokenClient<'_> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("token"))
+            .unwrap();
+        TokenClient::new(env, &token)
+    }
+
+    #[contractimpl]
+    impl YieldStrategy for MockStrategy {
+        fn deposit(_env: Env, amount: i128) -> i128 {
+            amount
+        }
+
+        fn withdraw(env: Env, to: Address, amount: i128) -> i128 {
+            let token = mock_token(&env);
+            let sent = amount.min(token.balance(&env.current_contract_address()));
+            token.transfer(&env.current_contract_address(), &to, &sent);
+            sent
+        }
+
+        fn total_assets(env: Env) -> i128 {
+            mock_token(&env).balance(&env.current_contract_address())
+        }
+    }
+
+    struct Setup {
+        env: Env,
+        client: NesteraContractClient<'static>,
+        admin: Address,
+        token: Address,
+        strategy: Address,
+        user: Address,
+    }
+
+    /// Contract with 10,000 of Flexi liquidity and a registered mock strategy
+    /// capped at 60% with a 40% buffer
+    fn setup() -> Setup {
+        let env = Env::default();
+        let client = NesteraContractClient::new(&env, &env.register(NesteraContract, ()));
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
+        let strategy = env.register(MockStrategy, ());
+        MockStrategyClient::new(&env, &strategy).init(&token);
+        client.set_strategy(&admin, &token, &strategy, &6_000, &4_000);
+
+        client.initialize_user(&user);
+        StellarAssetClient::new(&env, &token).mint(&user, &10_000);
+        client.deposit_flexi(&user, &10_000);
+
+        Setup {
+            env,
+            client,
+            admin,
+            token,
+            strategy,
+            user,
+        }
+    }
+
+    #[test]
+    fn test_allocation_is_capped_and_reported() {
+        let s = setup();
+
+        let result = s
+            .client
+            .try_allocate_to_strategy(&s.admin, &s.token, &6_001);
+        assert_eq!(result, Err(Ok(SavingsError::AmountExceedsLimit)));
+
+        s.client.allocate_to_strategy(&s.admin, &s.token, &4_000);
+
+        let allocation = s.client.get_strategy_allocation(&s.token);
+        assert_eq!(allocation.strategy, s.strategy);
+        assert_eq!(allocation.allocated, 4_000);
+        assert_eq!(allocation.total_assets, 4_000);
+        assert_eq!(allocation.liquidity, 10_000);
+        assert_eq!(allocation.liquid_balance, 6_000);
+    }
+
+    #[test]
+    fn test_allocation_keeps_liquid_buffer() {
+        let s = setup();
+        s.client.set_flexi_rate(&s.admin, &1_000);
+        s.env
+            .ledger()
+            .with_mut(|li| li.timestamp += 365 * 24 * 60 * 60);
+        s.client.claim_flexi_interest(&s.user);
+
+        // Liquidity is now 11,000 but only 10,000 is on hand, so allocating
+        // the full 60% cap would leave less than the 40% buffer
+        let result = s
+            .client
+            .try_allocate_to_strategy(&s.admin, &s.token, &6_000);
+        assert_eq!(result, Err(Ok(SavingsError::InsufficientBalance)));
+        s.client.allocate_to_strategy(&s.admin, &s.token, &5_600);
+    }
+
+    #[test]
+    fn test_withdrawal_recalls_from_strategy() {
+        let s = setup();
+        s.client.allocate_to_strategy(&s.admin, &s.token, &6_000);
+
+        s.client.withdraw_flexi(&s.user, &10_000);
+
+        let token = TokenClient::new(&s.env, &s.token);
+        assert_eq!(token.balance(&s.user), 10_000);
+        assert_eq!(token.balance(&s.strategy), 0);
+        assert_eq!(s.client.get_strategy_allocation(&s.token).allocated, 0);
+    }
+
+    #[test]
+    fn test_recall_returns_yield_to_custody() {
+        let s = setup();
+        s.client.allocate_to_strategy(&s.admin, &s.token, &6_000);
+        StellarAssetClient::new(&s.env, &s.token).mint(&s.strategy, &500);
+        assert_eq!(
+            s.client.get_strategy_allocation(&s.token).total_assets,
+            6_500
+        );
+
+        assert_eq!(
+            s.client.recall_from_strategy(&s.admin, &s.token, &6_500),
+            6_500
+        );
+        let allocation = s.client.get_strategy_allocation(&s.token);
+        assert_eq!(allocation.allocated, 0);
+        assert_eq!(allocation.liquid_balance, 10_500);
+    }
+
+    #[test]
+    fn test_set_strategy_validation() {
+        let s = setup();
+        let other = Address::generate(&s.env);
+
+        let result = s
+            .client
+            .try_set_strategy(&s.user, &s.token, &s.strategy, &5_000, &0);
+        assert_eq!(result, Err(Ok(SavingsError::Unauthorized)));
+
+        let result = s
+            .client
+            .try_set_strategy(&s.admin, &s.token, &s.strategy, &7_000, &4_000);
+        assert_eq!(result, Err(Ok(SavingsError::InvalidPlanConfig)));
+
+        // Funds must be recalled before switching to another strategy
+        s.client.allocate_to_strategy(&s.admin, &s.token, &1_000);
+        let result = s
+            .client
+            .try_set_strategy(&s.admin, &s.token, &other, &5_000, &0);
+        assert_eq!(result, Err(Ok(SavingsError::InvalidPlanConfig)));
+    }
+}
@@ -1,5 +1,9 @@
 #![cfg(test)]
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String,
+};
 
 use crate::{NesteraContract, NesteraContractClient, SavingsError};
 
@@ -13,9 +17,20 @@ fn setup() -> (Env, NesteraContractClient<'static>, Address) {
     env.mock_all_auths();
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+
     (env, client, admin)
 }
 
+/// Mints settlement asset tokens to `user` so group contributions can settle
+fn fund(env: &Env, client: &NesteraContractClient, user: &Address) {
+    let token = client.get_config().settlement_asset.unwrap();
+    StellarAssetClient::new(env, &token).mint(user, &10_000);
+}
+
 #[test]
 fn test_successful_break_group_save() {
     let (env, client, _admin) = setup();
@@ -27,8 +42,11 @@ fn test_successful_break_group_save() {
 
     // Initialize users
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
     client.initialize_user(&member1);
+    fund(&env, &client, &member1);
     client.initialize_user(&member2);
+    fund(&env, &client, &member2);
 
     // Create a group
     let group_id = client.create_group_save(
@@ -50,10 +68,10 @@ fn test_successful_break_group_save() {
     // Member2 joins
     client.join_group_save(&member2, &group_id);
 
-    // Members contribute
-    client.contribute_to_group_save(&creator, &group_id, &500);
-    client.contribute_to_group_save(&member1, &group_id, &300);
-    client.contribute_to_group_save(&member2, &group_id, &200);
+    // Members contribute the fixed amount
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    client.contribute_to_group_save(&member1, &group_id, &100);
+    client.contribute_to_group_save(&member2, &group_id, &100);
 
     // Member1 leaves
     client.break_group_save(&member1, &group_id);
@@ -71,8 +89,11 @@ fn test_break_group_updates_member_count() {
     env.mock_all_auths();
 
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
     client.initialize_user(&member1);
+    fund(&env, &client, &member1);
     client.initialize_user(&member2);
+    fund(&env, &client, &member2);
 
     let group_id = client.create_group_save(
         &creator,
@@ -104,6 +125,7 @@ fn test_break_group_updates_user_groups_list() {
 
     env.mock_all_auths();
     client.initialize_user(&user);
+    fund(&env, &client, &user);
 
     // Create first group
     let group1_id = client.create_group_save(
@@ -138,7 +160,7 @@ fn test_break_group_updates_user_groups_list() {
 
     // User should still be in group2
     // Verify by attempting to contribute (should succeed)
-    client.contribute_to_group_save(&user, &group2_id, &100);
+    client.contribute_to_group_save(&user, &group2_id, &50);
 }
 
 #[test]
@@ -149,7 +171,9 @@ fn test_break_group_refunds_contribution() {
 
     env.mock_all_auths();
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
     client.initialize_user(&member);
+    fund(&env, &client, &member);
 
     let group_id = client.create_group_save(
         &creator,
@@ -158,7 +182,7 @@ fn test_break_group_refunds_contribution() {
         &String::from_str(&env, "savings"),
         &5000,
         &0,
-        &100,
+        &500,
         &true,
         &1,
         &500,
@@ -168,9 +192,12 @@ fn test_break_group_refunds_contribution() {
 
     // Member contributes 500
     client.contribute_to_group_save(&member, &group_id, &500);
+    let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+    assert_eq!(token.balance(&member), 9_500);
 
     // Member leaves - should get refund
     client.break_group_save(&member, &group_id);
+    assert_eq!(token.balance(&member), 10_000);
 
     // Group's current_amount should be reduced by 500
 }
@@ -183,7 +210,9 @@ fn test_break_group_with_zero_contribution() {
 
     env.mock_all_auths();
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
     client.initialize_user(&member);
+    fund(&env, &client, &member);
 
     let group_id = client.create_group_save(
         &creator,
@@ -214,7 +243,9 @@ fn test_error_user_not_member() {
 
     env.mock_all_auths();
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
     client.initialize_user(&non_member);
+    fund(&env, &client, &non_member);
 
     let group_id = client.create_group_save(
         &creator,
@@ -241,6 +272,7 @@ fn test_error_group_completed() {
 
     env.mock_all_auths();
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
 
     let group_id = client.create_group_save(
         &creator,
@@ -249,7 +281,7 @@ fn test_error_group_completed() {
         &String::from_str(&env, "savings"),
         &100, // Low target to easily complete
         &0,
-        &10,
+        &100,
         &true,
         &1,
         &500,
@@ -270,6 +302,7 @@ fn test_error_group_not_found() {
 
     env.mock_all_auths();
     client.initialize_user(&user);
+    fund(&env, &client, &user);
 
     // Try to leave non-existent group
     let result = client.try_break_group_save(&user, &999);
@@ -284,6 +317,7 @@ fn test_error_user_not_found() {
 
     env.mock_all_auths();
     client.initialize_user(&creator);
+    fund(&env, &client, &creator);
 
     let group_id = client.create_group_save(
         &creator,
@@ -302,3 +336,601 @@ fn test_error_user_not_found() {
     let result = client.try_break_group_save(&non_existent_user, &group_id);
     assert_eq!(result.unwrap_err(), Ok(SavingsError::UserNotFound));
 }
+
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Group at a 10% GroupRate with a reserve in custody to pay interest from
+fn setup_interest_group(
+    target: i128,
+) -> (Env, NesteraContractClient<'static>, Address, Address, u64) {
+    let (env, client, admin) = setup();
+    client.set_group_rate(&admin, &1_000);
+    let token = client.get_config().settlement_asset.unwrap();
+    StellarAssetClient::new(&env, &token).mint(&client.address, &10_000);
+
+    let creator = Address::generate(&env);
+    let member = Address::generate(&env);
+    for user in [&creator, &member] {
+        client.initialize_user(user);
+        fund(&env, &client, user);
+    }
+
+    let group_id = client.create_group_save(
+        &creator,
+        &String::from_str(&env, "Interest Group"),
+        &String::from_str(&env, "Description"),
+        &String::from_str(&env, "savings"),
+        &target,
+        &0,
+        &1_000,
+        &true,
+        &1,
+        &1000,
+    );
+    client.join_group_save(&member, &group_id);
+
+    (env, client, creator, member, group_id)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_group_interest_split_by_time_weighted_contribution() {
+    let (env, client, creator, member, group_id) = setup_interest_group(3_000);
+    let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+    client.contribute_to_group_save(&creator, &group_id, &1_000);
+    advance(&env, YEAR / 2);
+    client.contribute_to_group_save(&member, &group_id, &1_000);
+    advance(&env, YEAR / 2);
+    // Completes the group, which stops further accrual
+    client.contribute_to_group_save(&creator, &group_id, &1_000);
+    advance(&env, YEAR);
+
+    // Creator held 1,000 for a year, member held 1,000 for half a year
+    assert_eq!(client.get_group_accrued_interest(&group_id), 150);
+    assert_eq!(client.get_group_interest_share(&group_id, &creator), 100);
+    assert_eq!(client.get_group_interest_share(&group_id, &member), 50);
+
+    let before = token.balance(&member);
+    assert_eq!(client.claim_group_interest(&member, &group_id), 50);
+    assert_eq!(token.balance(&member), before + 50);
+    assert_eq!(client.claim_group_interest(&creator, &group_id), 100);
+
+    let result = client.try_claim_group_interest(&member, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+}
+
+#[test]
+fn test_group_interest_requires_completion_and_is_forfeited_on_leave() {
+    let (env, client, creator, member, group_id) = setup_interest_group(5_000);
+
+    client.contribute_to_group_save(&creator, &group_id, &1_000);
+    client.contribute_to_group_save(&member, &group_id, &1_000);
+    advance(&env, YEAR);
+    assert_eq!(client.get_group_accrued_interest(&group_id), 200);
+
+    let result = client.try_claim_group_interest(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    client.break_group_save(&member, &group_id);
+    assert_eq!(client.get_group_accrued_interest(&group_id), 100);
+    assert_eq!(client.get_group_interest_share(&group_id, &member), 0);
+    assert_eq!(client.get_group_interest_share(&group_id, &creator), 100);
+}
+
+#[test]
+fn test_completed_group_returns_principal_and_keeps_interest() {
+    let (env, client, creator, member, group_id) = setup_interest_group(2_000);
+    let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+    client.contribute_to_group_save(&creator, &group_id, &1_000);
+    let result = client.try_withdraw_completed_group_save(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    advance(&env, YEAR);
+    client.contribute_to_group_save(&member, &group_id, &1_000);
+    let result = client.try_break_group_save(&member, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+
+    let before = token.balance(&member);
+    assert_eq!(
+        client.withdraw_completed_group_save(&member, &group_id),
+        1_000
+    );
+    assert_eq!(token.balance(&member), before + 1_000);
+    assert_eq!(client.get_user_holdings(&member).get(0).unwrap().group, 0);
+
+    let result = client.try_withdraw_completed_group_save(&member, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+
+    // The creator's interest share survives withdrawing the principal
+    client.withdraw_completed_group_save(&creator, &group_id);
+    assert_eq!(client.claim_group_interest(&creator, &group_id), 100);
+}
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+/// ROSCA group of three with a fixed payout order, created by the first
+/// member, whose other members have joined
+fn setup_rosca(
+    fixed_order: bool,
+) -> (
+    Env,
+    NesteraContractClient<'static>,
+    soroban_sdk::Vec<Address>,
+    u64,
+) {
+    let (env, client, _admin) = setup();
+    let members = soroban_sdk::vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env)
+    ];
+    for member in members.iter() {
+        client.initialize_user(&member);
+        fund(&env, &client, &member);
+    }
+
+    // The creator receives the pot last
+    let order = soroban_sdk::vec![
+        &env,
+        members.get(1).unwrap(),
+        members.get(2).unwrap(),
+        members.get(0).unwrap()
+    ];
+    let group_id = client.create_rosca_group(
+        &members.get(0).unwrap(),
+        &String::from_str(&env, "Ajo"),
+        &String::from_str(&env, "Weekly circle"),
+        &String::from_str(&env, "rosca"),
+        &100,
+        &WEEK,
+        &true,
+        &if fixed_order { Some(order) } else { None },
+    );
+    client.join_group_save(&members.get(1).unwrap(), &group_id);
+    client.join_group_save(&members.get(2).unwrap(), &group_id);
+
+    (env, client, members, group_id)
+}
+
+#[test]
+fn test_rosca_pays_each_cycle_pot_in_order() {
+    let (env, client, members, group_id) = setup_rosca(true);
+    let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+    let order = client.start_rosca(&members.get(0).unwrap(), &group_id);
+    assert_eq!(order.get(0).unwrap(), members.get(1).unwrap());
+
+    for (cycle, recipient) in order.iter().enumerate() {
+        if cycle > 0 {
+            // The next cycle opens one period after the previous one
+            let result = client.try_contribute_to_group_save(&recipient, &group_id, &100);
+            assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+            advance(&env, WEEK);
+        }
+        for member in members.iter() {
+            client.contribute_to_group_save(&member, &group_id, &100);
+        }
+        let before = token.balance(&recipient);
+        assert_eq!(client.payout_rosca_cycle(&group_id), recipient);
+        assert_eq!(token.balance(&recipient), before + 300);
+        assert_eq!(
+            client.get_rosca_state(&group_id).unwrap().current_cycle,
+            cycle as u32 + 1
+        );
+    }
+
+    // Everyone paid in 300 and received 300
+    for member in members.iter() {
+        assert_eq!(token.balance(&member), 10_000);
+        assert_eq!(client.get_user_holdings(&member).get(0).unwrap().group, 0);
+    }
+    let result = client.try_payout_rosca_cycle(&group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+}
+
+#[test]
+fn test_rosca_payout_requires_every_member_to_contribute() {
+    let (_env, client, members, group_id) = setup_rosca(true);
+    let result = client.try_payout_rosca_cycle(&group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    client.start_rosca(&members.get(0).unwrap(), &group_id);
+    client.contribute_to_group_save(&members.get(0).unwrap(), &group_id, &100);
+    client.contribute_to_group_save(&members.get(1).unwrap(), &group_id, &100);
+
+    let result = client.try_payout_rosca_cycle(&group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::GroupCycleIncomplete));
+
+    // Contributions are fixed at one per member per cycle
+    let late = members.get(2).unwrap();
+    let result = client.try_contribute_to_group_save(&late, &group_id, &50);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+    let result = client.try_contribute_to_group_save(&members.get(1).unwrap(), &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountExceedsLimit));
+
+    client.contribute_to_group_save(&late, &group_id, &100);
+    assert_eq!(
+        client.payout_rosca_cycle(&group_id),
+        members.get(1).unwrap()
+    );
+}
+
+#[test]
+fn test_rosca_missed_cycle_can_be_cancelled_after_its_deadline() {
+    let (env, client, members, group_id) = setup_rosca(true);
+    let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+    let creator = members.get(0).unwrap();
+    let result = client.try_cancel_rosca(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    // The first cycle pays out; one member misses the second
+    client.start_rosca(&creator, &group_id);
+    for member in members.iter() {
+        client.contribute_to_group_save(&member, &group_id, &100);
+    }
+    client.payout_rosca_cycle(&group_id);
+    advance(&env, WEEK);
+    client.contribute_to_group_save(&members.get(0).unwrap(), &group_id, &100);
+    client.contribute_to_group_save(&members.get(1).unwrap(), &group_id, &100);
+
+    let result = client.try_cancel_rosca(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+    advance(&env, WEEK);
+    let result = client.try_cancel_rosca(&members.get(1).unwrap(), &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+
+    // The unpaid cycle's contributions go back to the members who made them
+    assert_eq!(client.cancel_rosca(&creator, &group_id), 200);
+    assert_eq!(token.balance(&members.get(0).unwrap()), 9_900);
+    assert_eq!(token.balance(&members.get(1).unwrap()), 10_200);
+    assert_eq!(token.balance(&members.get(2).unwrap()), 9_900);
+    for member in members.iter() {
+        assert_eq!(client.get_user_holdings(&member).get(0).unwrap().group, 0);
+    }
+
+    let result = client.try_payout_rosca_cycle(&group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+    let result = client.try_cancel_rosca(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::PlanCompleted));
+}
+
+#[test]
+fn test_rosca_fully_funded_cycle_cannot_be_cancelled() {
+    let (env, client, members, group_id) = setup_rosca(true);
+    let creator = members.get(0).unwrap();
+    client.start_rosca(&creator, &group_id);
+    for member in members.iter() {
+        client.contribute_to_group_save(&member, &group_id, &100);
+    }
+
+    // The pot is still owed to the next member after the deadline
+    advance(&env, WEEK);
+    let result = client.try_cancel_rosca(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+    assert_eq!(
+        client.payout_rosca_cycle(&group_id),
+        members.get(1).unwrap()
+    );
+}
+
+#[test]
+fn test_rosca_membership_freezes_at_start() {
+    let (env, client, members, group_id) = setup_rosca(true);
+    let creator = members.get(0).unwrap();
+
+    // A fixed order only admits the members it lists
+    let outsider = Address::generate(&env);
+    client.initialize_user(&outsider);
+    let result = client.try_join_group_save(&outsider, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::TooEarly));
+
+    let result = client.try_start_rosca(&members.get(1).unwrap(), &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+    client.start_rosca(&creator, &group_id);
+
+    let result = client.try_break_group_save(&members.get(2).unwrap(), &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+    let result = client.try_start_rosca(&creator, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_rosca_randomised_order_covers_every_member() {
+    let (_env, client, members, group_id) = setup_rosca(false);
+    assert_eq!(
+        client
+            .get_rosca_state(&group_id)
+            .unwrap()
+            .payout_order
+            .len(),
+        0
+    );
+
+    let order = client.start_rosca(&members.get(0).unwrap(), &group_id);
+    assert_eq!(order.len(), 3);
+    for member in members.iter() {
+        assert!(order.contains(&member));
+    }
+    assert_eq!(
+        client.get_rosca_state(&group_id).unwrap().payout_order,
+        order
+    );
+}
+
+/// Creates a group as a new user and returns them with the group ID
+fn create_test_group(env: &Env, client: &NesteraContractClient, is_public: bool) -> (Address, u64) {
+    let creator = Address::generate(env);
+    client.initialize_user(&creator);
+    let group_id = client.create_group_save(
+        &creator,
+        &String::from_str(env, "Circle"),
+        &String::from_str(env, "Friends only"),
+        &String::from_str(env, "savings"),
+        &10_000,
+        &0,
+        &100,
+        &is_public,
+        &1,
+        &1000,
+    );
+    (creator, group_id)
+}
+
+/// A user is a member if they can contribute to the group
+fn is_group_member(
+    env: &Env,
+    client: &NesteraContractClient,
+    user: &Address,
+    group_id: u64,
+) -> bool {
+    fund(env, client, user);
+    client
+        .try_contribute_to_group_save(user, &group_id, &100)
+        .is_ok()
+}
+
+fn new_user(env: &Env, client: &NesteraContractClient) -> Address {
+    let user = Address::generate(env);
+    client.initialize_user(&user);
+    user
+}
+
+#[test]
+fn test_private_group_admits_invited_users() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_test_group(&env, &client, false);
+    let invitee = new_user(&env, &client);
+    let stranger = new_user(&env, &client);
+
+    let result = client.try_join_group_save(&invitee, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    let result = client.try_invite_to_group(&invitee, &group_id, &stranger);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+    client.invite_to_group(&creator, &group_id, &invitee);
+    client.invite_to_group(&creator, &group_id, &stranger);
+    assert_eq!(client.get_group_invites(&group_id).len(), 2);
+
+    client.join_group_save(&invitee, &group_id);
+    assert_eq!(
+        client.get_group_invites(&group_id),
+        soroban_sdk::vec![&env, stranger.clone()]
+    );
+
+    client.revoke_group_invite(&creator, &group_id, &stranger);
+    let result = client.try_join_group_save(&stranger, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+    assert_eq!(client.get_group_invites(&group_id).len(), 0);
+
+    // Members cannot be invited again
+    let result = client.try_invite_to_group(&creator, &group_id, &invitee);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_invite_codes_are_redeemed_once() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_test_group(&env, &client, false);
+    let joiner = new_user(&env, &client);
+    let code = soroban_sdk::Bytes::from_slice(&env, b"summer-2026");
+    let code_hash = crate::invites::invite_code_hash(&env, code.clone(), &joiner);
+
+    client.add_group_invite_code(&creator, &group_id, &code_hash);
+    assert_eq!(
+        client.get_group_invite_codes(&group_id),
+        soroban_sdk::vec![&env, code_hash]
+    );
+
+    let wrong = soroban_sdk::Bytes::from_slice(&env, b"winter-2026");
+    let result = client.try_join_group_with_code(&joiner, &group_id, &wrong);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+
+    // Someone who sees the code cannot redeem it first
+    let front_runner = new_user(&env, &client);
+    let result = client.try_join_group_with_code(&front_runner, &group_id, &code);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+
+    client.join_group_with_code(&joiner, &group_id, &code);
+    assert!(is_group_member(&env, &client, &joiner, group_id));
+    assert_eq!(client.get_group_invite_codes(&group_id).len(), 0);
+
+    let result = client.try_join_group_with_code(&joiner, &group_id, &code);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::Unauthorized));
+}
+
+#[test]
+fn test_public_group_join_approval() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_test_group(&env, &client, true);
+    let applicant = new_user(&env, &client);
+    let rejected = new_user(&env, &client);
+
+    // Without approval required there is nothing to request
+    let result = client.try_request_to_join_group(&applicant, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    client.set_group_join_approval(&creator, &group_id, &true);
+    assert!(client.group_requires_approval(&group_id));
+    let result = client.try_join_group_save(&applicant, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+
+    client.request_to_join_group(&applicant, &group_id);
+    client.request_to_join_group(&rejected, &group_id);
+    assert_eq!(client.get_group_join_requests(&group_id).len(), 2);
+
+    client.respond_to_join_request(&creator, &group_id, &applicant, &true);
+    client.respond_to_join_request(&creator, &group_id, &rejected, &false);
+    assert_eq!(client.get_group_join_requests(&group_id).len(), 0);
+    assert!(is_group_member(&env, &client, &applicant, group_id));
+    assert!(!is_group_member(&env, &client, &rejected, group_id));
+
+    let result = client.try_respond_to_join_request(&creator, &group_id, &rejected, &true);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+fn create_typed_group(
+    env: &Env,
+    client: &NesteraContractClient,
+    contribution_type: u32,
+    contribution_amount: i128,
+) -> (Address, u64) {
+    let creator = new_user(env, client);
+    fund(env, client, &creator);
+    let group_id = client.create_group_save(
+        &creator,
+        &String::from_str(env, "Typed"),
+        &String::from_str(env, "Contribution rules"),
+        &String::from_str(env, "savings"),
+        &1_000,
+        &contribution_type,
+        &contribution_amount,
+        &true,
+        &0,
+        &(4 * WEEK),
+    );
+    (creator, group_id)
+}
+
+#[test]
+fn test_fixed_group_takes_exact_amount_once_per_period() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 0, 100);
+    client.set_group_period(&creator, &group_id, &WEEK);
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &150);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountExceedsLimit));
+
+    advance(&env, WEEK);
+    client.contribute_to_group_save(&creator, &group_id, &100);
+
+    // The period is fixed once contributions are in
+    let result = client.try_set_group_period(&creator, &group_id, &YEAR);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_fixed_group_period_defaults_to_the_whole_group() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 0, 100);
+
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    advance(&env, WEEK);
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &100);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountExceedsLimit));
+}
+
+#[test]
+fn test_flexible_group_enforces_minimum() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 1, 100);
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &99);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+    client.contribute_to_group_save(&creator, &group_id, &100);
+    client.contribute_to_group_save(&creator, &group_id, &250);
+
+    let result = client.try_set_group_period(&creator, &group_id, &WEEK);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+}
+
+#[test]
+fn test_percentage_group_sizes_contributions_by_member_share() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 2, 1);
+    let members = [new_user(&env, &client), new_user(&env, &client)];
+    for member in members.iter() {
+        client.join_group_save(member, &group_id);
+        fund(&env, &client, member);
+    }
+
+    // Each of the three members owes a third of the 1_000 target, rounded up
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &333);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+    client.contribute_to_group_save(&creator, &group_id, &334);
+    for member in members.iter() {
+        client.contribute_to_group_save(member, &group_id, &334);
+    }
+
+    let result = client.try_contribute_to_group_save(&creator, &group_id, &334);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::AmountExceedsLimit));
+    // Together the rounded-up shares complete the group
+    assert_eq!(
+        client.withdraw_completed_group_save(&creator, &group_id),
+        334
+    );
+}
+
+#[test]
+fn test_percentage_share_is_fixed_for_the_period() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 2, 1);
+    client.set_group_period(&creator, &group_id, &WEEK);
+    let member = new_user(&env, &client);
+    client.join_group_save(&member, &group_id);
+    fund(&env, &client, &member);
+
+    // A member joining after the first payment does not change this
+    // period's share of half the target
+    client.contribute_to_group_save(&creator, &group_id, &500);
+    let late = new_user(&env, &client);
+    client.join_group_save(&late, &group_id);
+    fund(&env, &client, &late);
+    let result = client.try_contribute_to_group_save(&member, &group_id, &334);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidAmount));
+    client.contribute_to_group_save(&member, &group_id, &500);
+}
+
+#[test]
+fn test_group_member_limit() {
+    let (env, client, _admin) = setup();
+    let (creator, group_id) = create_typed_group(&env, &client, 1, 100);
+    let member = new_user(&env, &client);
+    client.join_group_save(&member, &group_id);
+
+    let result = client.try_set_group_max_members(&creator, &group_id, &1);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::InvalidGroupConfig));
+    client.set_group_max_members(&creator, &group_id, &2);
+
+    let late = new_user(&env, &client);
+    let result = client.try_join_group_save(&late, &group_id);
+    assert_eq!(result.unwrap_err(), Ok(SavingsError::GroupFull));
+
+    // A leaving member frees a place
+    client.break_group_save(&member, &group_id);
+    client.join_group_save(&late, &group_id);
+
+    client.set_group_max_members(&creator, &group_id, &0);
+    client.join_group_save(&member, &group_id);
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
 
 use crate::storage_types::{DataKey, GoalSave, LockSave, SavingsPlan};
 
@@ -29,10 +29,10 @@ pub fn extend_instance_ttl(env: &Env) {
 }
 
 /// Extends TTL for user-related storage entries
-/// Includes: User data, FlexiBalance, TotalBalance
+/// Includes: User data, per-asset FlexiBalance entries, TotalBalance
 pub fn extend_user_ttl(env: &Env, user: &Address) {
     let user_key = DataKey::User(user.clone());
-    let flexi_key = DataKey::FlexiBalance(user.clone());
+    let flexi_assets_key = DataKey::UserFlexiAssets(user.clone());
     let total_key = DataKey::TotalBalance(user.clone());
 
     // Only extend TTL if the key exists
@@ -42,10 +42,30 @@ pub fn extend_user_ttl(env: &Env, user: &Address) {
             .extend_ttl(&user_key, LOW_THRESHOLD, EXTEND_TO);
     }
 
-    if env.storage().persistent().has(&flexi_key) {
+    if let Some(assets) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<Address>>(&flexi_assets_key)
+    {
         env.storage()
             .persistent()
-            .extend_ttl(&flexi_key, LOW_THRESHOLD, EXTEND_TO);
+            .extend_ttl(&flexi_assets_key, LOW_THRESHOLD, EXTEND_TO);
+
+        for asset in assets.iter() {
+            let flexi_key = DataKey::FlexiBalance(user.clone(), asset.clone());
+            if env.storage().persistent().has(&flexi_key) {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&flexi_key, LOW_THRESHOLD, EXTEND_TO);
+            }
+
+            let interest_key = DataKey::FlexiInterest(user.clone(), asset);
+            if env.storage().persistent().has(&interest_key) {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&interest_key, LOW_THRESHOLD, EXTEND_TO);
+            }
+        }
     }
 
     if env.storage().persistent().has(&total_key) {
@@ -1,7 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::{NesteraContract, NesteraContractClient};
-    use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+    use crate::{
+        AutoSaveDestination, AutoSaveStatus, MissedIntervalPolicy, NesteraContract,
+        NesteraContractClient,
+    };
+    use soroban_sdk::{
+        testutils::Address as _, token::StellarAssetClient, Address, BytesN, Env, Symbol,
+    };
+
+    /// Expiration ledger recorded for AutoSave allowances in these tests
+    const ALLOWANCE_EXPIRY: u32 = 10_000;
 
     fn setup_test_env() -> (Env, NesteraContractClient<'static>) {
         let env = Env::default();
@@ -10,6 +18,18 @@ mod tests {
         (env, client)
     }
 
+    /// Initializes the contract with a test settlement asset and returns its address
+    fn setup_settlement_asset(env: &Env, client: &NesteraContractClient) -> Address {
+        let admin = Address::generate(env);
+        client.initialize(&admin, &BytesN::from_array(env, &[1u8; 32]));
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+        client.set_lock_rate(&admin, &0, &500);
+        token
+    }
+
     #[test]
     fn test_ttl_extension_on_user_creation() {
         let (env, client) = setup_test_env();
@@ -37,6 +57,8 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&user);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&user, &1000);
 
         // Deposit to flexi - should extend TTL
         assert!(client.try_deposit_flexi(&user, &1000).is_ok());
@@ -61,6 +83,8 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&user);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&user, &10_000);
 
         // Create lock save - should extend TTL
         let lock_id = client.create_lock_save(&user, &5000, &(30 * 24 * 60 * 60));
@@ -84,11 +108,13 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&user);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&user, &10_000);
 
         let goal_name = Symbol::new(&env, "vacation");
 
         // Create goal save - should extend TTL
-        let goal_id = client.create_goal_save(&user, &goal_name, &10000, &1000);
+        let goal_id = client.create_goal_save(&user, &goal_name, &10000, &1000, &None);
         assert_eq!(goal_id, 1);
 
         // Deposit to goal - should extend TTL
@@ -112,11 +138,13 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&user);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&user, &10_000);
 
         let goal_name = Symbol::new(&env, "car");
 
         // Create completed goal
-        let goal_id = client.create_goal_save(&user, &goal_name, &5000, &5000);
+        let goal_id = client.create_goal_save(&user, &goal_name, &5000, &5000, &None);
 
         // Get goal details - completed goals should still extend TTL (but shorter)
         let goal = client.get_goal_save_detail(&goal_id);
@@ -142,14 +170,21 @@ mod tests {
         client.initialize_user(&user);
 
         // Create autosave schedule - should extend TTL
-        let schedule_id =
-            client.create_autosave(&user, &100, &(24 * 60 * 60), &env.ledger().timestamp());
+        let schedule_id = client.create_autosave(
+            &user,
+            &100,
+            &(24 * 60 * 60),
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         assert_eq!(schedule_id, 1);
 
         // Get autosave - should extend TTL
         let schedule = client.get_autosave(&1);
         assert!(schedule.is_some());
-        assert!(schedule.unwrap().is_active);
+        assert_eq!(schedule.unwrap().status, AutoSaveStatus::Active);
 
         // Get user autosaves - should extend TTL
         let schedules = client.get_user_autosaves(&user);
@@ -196,6 +231,8 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&user);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&user, &10_000);
 
         // Create a lock save
         let lock_id = client.create_lock_save(&user, &5000, &(30 * 24 * 60 * 60));
@@ -220,6 +257,8 @@ mod tests {
 
         // Initialize user
         client.initialize_user(&creator);
+        let token = setup_settlement_asset(&env, &client);
+        StellarAssetClient::new(&env, &token).mint(&creator, &10_000);
 
         // Create group save - should extend TTL
         let group_id = client.create_group_save(
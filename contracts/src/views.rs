@@ -1,4 +1,5 @@
 use crate::errors::SavingsError;
+use crate::math::{self, Rounding};
 use crate::storage_types::{
     DataKey, GoalSaveView, GroupSaveView, LockSaveView, PlanType, SavingsPlan, User,
 };
@@ -31,6 +32,10 @@ fn to_goal_save(plan: &SavingsPlan) -> Option<GoalSaveView> {
             target_amount: *target_amount,
             start_time: plan.start_time,
             interest_rate: plan.interest_rate,
+            // Legacy savings plans do not track goal interest
+            accrued_interest: 0,
+            deadline: None,
+            missed_deadline: false,
             is_completed: plan.is_completed,
             contribution_type: *contribution_type,
             goal_name: goal_name.clone(),
@@ -211,6 +216,76 @@ pub fn get_goal_save(env: &Env, user: Address, goal_id: u64) -> Result<GoalSaveV
     to_goal_save(&plan).ok_or(SavingsError::PlanNotFound)
 }
 
+/// Builds the view of a Goal Save, including interest accrued up to now.
+pub fn get_goal_save_view(env: &Env, goal_id: u64) -> Result<GoalSaveView, SavingsError> {
+    let goal_save = crate::goal::get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+
+    Ok(GoalSaveView {
+        plan_id: goal_save.id,
+        balance: goal_save.current_amount,
+        target_amount: goal_save.target_amount,
+        start_time: goal_save.start_time,
+        interest_rate: goal_save.interest_rate,
+        accrued_interest: crate::goal::get_accrued_goal_interest(env, &goal_save)?,
+        deadline: goal_save.deadline,
+        missed_deadline: crate::goal::missed_deadline(env, &goal_save),
+        is_completed: goal_save.is_completed,
+        contribution_type: 0,
+        goal_name: goal_save.goal_name,
+    })
+}
+
+/// Gross deposit needed every `period_secs` to reach a goal's target by its
+/// deadline, including the platform deposit fee.
+///
+/// Once the deadline has passed, the whole shortfall is due in one period.
+///
+/// # Errors
+/// * `SavingsError::PlanNotFound` - If the goal does not exist
+/// * `SavingsError::InvalidTimestamp` - If `period_secs` is zero
+/// * `SavingsError::InvalidPlanConfig` - If the goal has no deadline
+pub fn get_goal_required_contribution(
+    env: &Env,
+    goal_id: u64,
+    period_secs: u64,
+) -> Result<i128, SavingsError> {
+    if period_secs == 0 {
+        return Err(SavingsError::InvalidTimestamp);
+    }
+
+    let goal_save = crate::goal::get_goal_save(env, goal_id).ok_or(SavingsError::PlanNotFound)?;
+    let deadline = goal_save.deadline.ok_or(SavingsError::InvalidPlanConfig)?;
+
+    let shortfall = goal_save
+        .target_amount
+        .checked_sub(goal_save.current_amount)
+        .ok_or(SavingsError::Underflow)?;
+    if shortfall <= 0 || goal_save.is_withdrawn {
+        return Ok(0);
+    }
+
+    let remaining = deadline.saturating_sub(env.ledger().timestamp());
+    let periods = remaining.div_ceil(period_secs).max(1);
+    let net_per_period = math::mul_div(shortfall, 1, periods as i128, Rounding::Up)?;
+
+    // Gross up so the deposit still covers the shortfall after the fee
+    let fee_bps: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::PlatformFee)
+        .unwrap_or(0);
+    let kept_bps = math::BPS_DENOMINATOR
+        .checked_sub(fee_bps as i128)
+        .filter(|kept| *kept > 0)
+        .ok_or(SavingsError::InvalidPlanConfig)?;
+    math::mul_div(
+        net_per_period,
+        math::BPS_DENOMINATOR,
+        kept_bps,
+        Rounding::Up,
+    )
+}
+
 // ===========================================================================
 // Group Save Views
 // ===========================================================================
@@ -1,21 +1,52 @@
 #[cfg(test)]
 mod autosave_tests {
-    use soroban_sdk::{testutils::Address as _, Address, Env};
-    use Nestera::{NesteraContract, NesteraContractClient};
+    use soroban_sdk::{
+        testutils::{Address as _, Events, Ledger},
+        token::{StellarAssetClient, TokenClient},
+        Address, BytesN, Env, IntoVal, String, Symbol, TryFromVal,
+    };
+    use Nestera::{
+        AutoSaveDestination, AutoSaveStatus, KeeperEarning, KeeperTip, MissedIntervalPolicy,
+        NesteraContract, NesteraContractClient, SavingsError,
+    };
+
+    /// Expiration ledger of the allowances users grant for AutoSave pulls
+    const ALLOWANCE_EXPIRY: u32 = 10_000;
 
     fn setup_test_contract() -> (Env, NesteraContractClient<'static>, Address) {
+        let (env, client, _admin, user) = setup_with_admin();
+        (env, client, user)
+    }
+
+    fn setup_with_admin() -> (Env, NesteraContractClient<'static>, Address, Address) {
         let env = Env::default();
         env.mock_all_auths();
 
         let contract_id = env.register(NesteraContract, ());
         let client = NesteraContractClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &BytesN::from_array(&env, &[1u8; 32]));
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_settlement_asset(&admin, &token);
+
         let user = Address::generate(&env);
 
-        // Initialize user
+        // Initialize and fund user
         client.initialize_user(&user);
+        fund(&env, &client, &user, 1_000_000);
 
-        (env, client, user)
+        (env, client, admin, user)
+    }
+
+    /// Mints settlement asset tokens to `user` and approves the contract to
+    /// pull them, so AutoSave deposits can settle without a signature
+    fn fund(env: &Env, client: &NesteraContractClient, user: &Address, amount: i128) {
+        let token = client.get_config().settlement_asset.unwrap();
+        StellarAssetClient::new(env, &token).mint(user, &amount);
+        TokenClient::new(env, &token).approve(user, &client.address, &amount, &ALLOWANCE_EXPIRY);
     }
 
     #[test]
@@ -26,13 +57,21 @@ mod autosave_tests {
         let interval = 86400; // 1 day
         let start_time = env.ledger().timestamp();
 
-        let result = client.create_autosave(&user, &amount, &interval, &start_time);
+        let result = client.create_autosave(
+            &user,
+            &amount,
+            &interval,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         assert_eq!(result, 1); // First schedule ID
 
         // Verify schedule was stored
         let schedules = client.get_user_autosaves(&user);
         assert_eq!(schedules.len(), 1);
-        assert_eq!(schedules.get(0).unwrap(), 1);
+        assert_eq!(schedules.get(0).unwrap().id, 1);
     }
 
     #[test]
@@ -40,7 +79,15 @@ mod autosave_tests {
         let (env, client, user) = setup_test_contract();
 
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            client.create_autosave(&user, &0, &86400, &env.ledger().timestamp());
+            client.create_autosave(
+                &user,
+                &0,
+                &86400,
+                &env.ledger().timestamp(),
+                &AutoSaveDestination::Flexi,
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            );
         }));
         assert!(result.is_err()); // Should panic with InvalidAmount
     }
@@ -50,7 +97,15 @@ mod autosave_tests {
         let (env, client, user) = setup_test_contract();
 
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            client.create_autosave(&user, &1000, &0, &env.ledger().timestamp());
+            client.create_autosave(
+                &user,
+                &1000,
+                &0,
+                &env.ledger().timestamp(),
+                &AutoSaveDestination::Flexi,
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            );
         }));
         assert!(result.is_err()); // Should panic with InvalidTimestamp
     }
@@ -66,7 +121,15 @@ mod autosave_tests {
         let user = Address::generate(&env); // Not initialized
 
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            client.create_autosave(&user, &1000, &86400, &env.ledger().timestamp());
+            client.create_autosave(
+                &user,
+                &1000,
+                &86400,
+                &env.ledger().timestamp(),
+                &AutoSaveDestination::Flexi,
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            );
         }));
         assert!(result.is_err()); // Should panic with UserNotFound
     }
@@ -79,7 +142,15 @@ mod autosave_tests {
         let interval = 86400;
         let start_time = env.ledger().timestamp();
 
-        let schedule_id = client.create_autosave(&user, &amount, &interval, &start_time);
+        let schedule_id = client.create_autosave(
+            &user,
+            &amount,
+            &interval,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         // Get initial balance
         let initial_balance = client.get_flexi_balance(&user);
@@ -100,7 +171,15 @@ mod autosave_tests {
         let interval = 86400;
         let start_time = env.ledger().timestamp() + 10000; // Future time
 
-        let schedule_id = client.create_autosave(&user, &amount, &interval, &start_time);
+        let schedule_id = client.create_autosave(
+            &user,
+            &amount,
+            &interval,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         // Try to execute before due time
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
@@ -113,7 +192,15 @@ mod autosave_tests {
     fn test_cancel_autosave_success() {
         let (env, client, user) = setup_test_contract();
 
-        let schedule_id = client.create_autosave(&user, &1000, &86400, &env.ledger().timestamp());
+        let schedule_id = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         // Cancel the schedule
         client.cancel_autosave(&user, &schedule_id);
@@ -129,7 +216,15 @@ mod autosave_tests {
     fn test_cancel_autosave_unauthorized() {
         let (env, client, user) = setup_test_contract();
 
-        let schedule_id = client.create_autosave(&user, &1000, &86400, &env.ledger().timestamp());
+        let schedule_id = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let other_user = Address::generate(&env);
         client.initialize_user(&other_user);
@@ -147,20 +242,44 @@ mod autosave_tests {
 
         let start_time = env.ledger().timestamp();
 
-        let id1 = client.create_autosave(&user, &1000, &86400, &start_time);
-        let id2 = client.create_autosave(&user, &2000, &172800, &start_time);
+        let id1 = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let id2 = client.create_autosave(
+            &user,
+            &2000,
+            &172800,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let schedules = client.get_user_autosaves(&user);
         assert_eq!(schedules.len(), 2);
-        assert_eq!(schedules.get(0).unwrap(), id1);
-        assert_eq!(schedules.get(1).unwrap(), id2);
+        assert_eq!(schedules.get(0).unwrap().id, id1);
+        assert_eq!(schedules.get(1).unwrap().id, id2);
     }
 
     #[test]
     fn test_execute_cancelled_schedule() {
         let (env, client, user) = setup_test_contract();
 
-        let schedule_id = client.create_autosave(&user, &1000, &86400, &env.ledger().timestamp());
+        let schedule_id = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         // Cancel the schedule
         client.cancel_autosave(&user, &schedule_id);
@@ -181,12 +300,36 @@ mod autosave_tests {
         let start_time = env.ledger().timestamp(); // immediately due
 
         // Create three due schedules
-        let id1 = client.create_autosave(&user, &500, &86400, &start_time);
-        let id2 = client.create_autosave(&user, &300, &86400, &start_time);
-        let id3 = client.create_autosave(&user, &200, &86400, &start_time);
+        let id1 = client.create_autosave(
+            &user,
+            &500,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let id2 = client.create_autosave(
+            &user,
+            &300,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let id3 = client.create_autosave(
+            &user,
+            &200,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let schedule_ids = soroban_sdk::vec![&env, id1, id2, id3];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         // All should succeed
         assert_eq!(results.len(), 3);
@@ -205,11 +348,27 @@ mod autosave_tests {
 
         let future_time = env.ledger().timestamp() + 100_000; // far in the future
 
-        let id1 = client.create_autosave(&user, &1000, &86400, &future_time);
-        let id2 = client.create_autosave(&user, &2000, &86400, &future_time);
+        let id1 = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &future_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let id2 = client.create_autosave(
+            &user,
+            &2000,
+            &86400,
+            &future_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let schedule_ids = soroban_sdk::vec![&env, id1, id2];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         // Both should be skipped (not due)
         assert_eq!(results.len(), 2);
@@ -227,7 +386,7 @@ mod autosave_tests {
 
         // Use IDs that don't exist
         let schedule_ids = soroban_sdk::vec![&env, 999u64, 888u64, 777u64];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         // All should be false (not found)
         assert_eq!(results.len(), 3);
@@ -242,14 +401,30 @@ mod autosave_tests {
 
         let start_time = env.ledger().timestamp();
 
-        let id1 = client.create_autosave(&user, &1000, &86400, &start_time);
-        let id2 = client.create_autosave(&user, &2000, &86400, &start_time);
+        let id1 = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let id2 = client.create_autosave(
+            &user,
+            &2000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         // Cancel id1 (makes it inactive)
         client.cancel_autosave(&user, &id1);
 
         let schedule_ids = soroban_sdk::vec![&env, id1, id2];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         // id1 should be false (inactive), id2 should be true (due and active)
         assert_eq!(results.len(), 2);
@@ -269,20 +444,52 @@ mod autosave_tests {
         let future_time = env.ledger().timestamp() + 100_000;
 
         // id1: due and active -> should succeed
-        let id1 = client.create_autosave(&user, &500, &86400, &start_time);
+        let id1 = client.create_autosave(
+            &user,
+            &500,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         // id2: not due -> should be skipped
-        let id2 = client.create_autosave(&user, &300, &86400, &future_time);
+        let id2 = client.create_autosave(
+            &user,
+            &300,
+            &86400,
+            &future_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         // id3: due and active -> should succeed
-        let id3 = client.create_autosave(&user, &200, &86400, &start_time);
+        let id3 = client.create_autosave(
+            &user,
+            &200,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         // id4: cancelled -> should be skipped
-        let id4 = client.create_autosave(&user, &1000, &86400, &start_time);
+        let id4 = client.create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         client.cancel_autosave(&user, &id4);
 
         // Also include a non-existent ID
         let fake_id: u64 = 999;
 
         let schedule_ids = soroban_sdk::vec![&env, id1, id2, id3, id4, fake_id];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         assert_eq!(results.len(), 5);
         assert_eq!(results.get(0).unwrap(), true); // id1: due, active -> executed
@@ -303,10 +510,18 @@ mod autosave_tests {
         let start_time = env.ledger().timestamp();
         let interval = 86400u64;
 
-        let id1 = client.create_autosave(&user, &1000, &interval, &start_time);
+        let id1 = client.create_autosave(
+            &user,
+            &1000,
+            &interval,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let schedule_ids = soroban_sdk::vec![&env, id1];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         assert_eq!(results.get(0).unwrap(), true);
 
@@ -320,7 +535,7 @@ mod autosave_tests {
         let (env, client, _user) = setup_test_contract();
 
         let schedule_ids: soroban_sdk::Vec<u64> = soroban_sdk::vec![&env];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         // Should return empty results
         assert_eq!(results.len(), 0);
@@ -332,16 +547,33 @@ mod autosave_tests {
 
         let user2 = Address::generate(&env);
         client.initialize_user(&user2);
+        fund(&env, &client, &user2, 1_000_000);
 
         let start_time = env.ledger().timestamp();
 
         // user1 schedules
-        let id1 = client.create_autosave(&user1, &500, &86400, &start_time);
+        let id1 = client.create_autosave(
+            &user1,
+            &500,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
         // user2 schedules
-        let id2 = client.create_autosave(&user2, &800, &86400, &start_time);
+        let id2 = client.create_autosave(
+            &user2,
+            &800,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
 
         let schedule_ids = soroban_sdk::vec![&env, id1, id2];
-        let results = client.execute_due_autosaves(&schedule_ids);
+        let results = client.execute_due_autosaves(&None, &schedule_ids);
 
         assert_eq!(results.get(0).unwrap(), true);
         assert_eq!(results.get(1).unwrap(), true);
@@ -350,4 +582,733 @@ mod autosave_tests {
         assert_eq!(client.get_flexi_balance(&user1), 500);
         assert_eq!(client.get_flexi_balance(&user2), 800);
     }
+
+    #[test]
+    fn test_autosave_into_goal_deactivates_on_completion() {
+        let (env, client, user) = setup_test_contract();
+
+        let goal_id = client.create_goal_save(&user, &Symbol::new(&env, "car"), &2_000, &0, &None);
+        let start_time = env.ledger().timestamp();
+        let schedule_id = client.create_autosave(
+            &user,
+            &1_000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Goal(goal_id),
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+
+        let ids = soroban_sdk::vec![&env, schedule_id];
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(client.get_goal_save_detail(&goal_id).current_amount, 1_000);
+
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert!(client.get_goal_save_detail(&goal_id).is_completed);
+        assert_eq!(
+            client.get_autosave(&schedule_id).unwrap().status,
+            AutoSaveStatus::Deactivated
+        );
+
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(client.get_goal_save_detail(&goal_id).current_amount, 2_000);
+    }
+
+    #[test]
+    fn test_autosave_into_group_deactivates_after_leaving() {
+        let (env, client, user) = setup_test_contract();
+
+        let group_id = client.create_group_save(
+            &user,
+            &String::from_str(&env, "Group"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "savings"),
+            &10_000,
+            &0,
+            &500,
+            &true,
+            &1,
+            &1_000_000,
+        );
+        let start_time = env.ledger().timestamp();
+        let schedule_id = client.create_autosave(
+            &user,
+            &500,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Group(group_id),
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+
+        let ids = soroban_sdk::vec![&env, schedule_id];
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        let token = client.get_config().settlement_asset.unwrap();
+        assert_eq!(client.get_solvency_report(&token).group, 500);
+
+        client.break_group_save(&user, &group_id);
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.status, AutoSaveStatus::Deactivated);
+
+        // Deactivation is not a failed execution
+        assert_eq!(schedule.consecutive_failures, 0);
+        assert_eq!(schedule.last_failure_reason, None);
+        let fail_topics: soroban_sdk::Vec<soroban_sdk::Val> =
+            (Symbol::new(&env, "as_fail"), user.clone(), schedule_id).into_val(&env);
+        assert!(!env
+            .events()
+            .all()
+            .iter()
+            .any(|(_, topics, _)| topics == fail_topics));
+    }
+
+    #[test]
+    fn test_autosave_opens_new_lock_each_run() {
+        let (env, client, admin, user) = setup_with_admin();
+        client.set_lock_rate(&admin, &30, &500);
+
+        let duration = 30 * 86400;
+        let start_time = env.ledger().timestamp();
+        let schedule_id = client.create_autosave(
+            &user,
+            &1_000,
+            &86400,
+            &start_time,
+            &AutoSaveDestination::Lock(duration),
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+
+        client.execute_autosave(&schedule_id);
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.execute_autosave(&schedule_id);
+
+        let locks = client.get_user_lock_saves(&user);
+        assert_eq!(locks.len(), 2);
+        let lock = client.get_lock_save_detail(&locks.get(1).unwrap());
+        assert_eq!(lock.amount, 1_000);
+        assert_eq!(lock.maturity_time, lock.start_time + duration);
+    }
+
+    #[test]
+    fn test_create_autosave_validates_destination() {
+        let (env, client, admin, user) = setup_with_admin();
+        let start_time = env.ledger().timestamp();
+
+        let other = Address::generate(&env);
+        client.initialize_user(&other);
+        fund(&env, &client, &other, 1_000_000);
+        let others_goal =
+            client.create_goal_save(&other, &Symbol::new(&env, "trip"), &5_000, &0, &None);
+        assert_eq!(
+            client.try_create_autosave(
+                &user,
+                &100,
+                &86400,
+                &start_time,
+                &AutoSaveDestination::Goal(others_goal),
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            ),
+            Err(Ok(SavingsError::Unauthorized))
+        );
+
+        let group_id = client.create_group_save(
+            &other,
+            &String::from_str(&env, "Group"),
+            &String::from_str(&env, "Description"),
+            &String::from_str(&env, "savings"),
+            &10_000,
+            &0,
+            &100,
+            &true,
+            &1,
+            &1_000_000,
+        );
+        assert_eq!(
+            client.try_create_autosave(
+                &user,
+                &100,
+                &86400,
+                &start_time,
+                &AutoSaveDestination::Group(group_id),
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            ),
+            Err(Ok(SavingsError::NotGroupMember))
+        );
+
+        // No LockRate tier covers the duration yet
+        let duration = 7 * 86400;
+        assert_eq!(
+            client.try_create_autosave(
+                &user,
+                &100,
+                &86400,
+                &start_time,
+                &AutoSaveDestination::Lock(duration),
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            ),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+        client.set_lock_rate(&admin, &7, &300);
+        assert!(client
+            .try_create_autosave(
+                &user,
+                &100,
+                &86400,
+                &start_time,
+                &AutoSaveDestination::Lock(duration),
+                &ALLOWANCE_EXPIRY,
+                &MissedIntervalPolicy::Single,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_keeper_executes_against_allowance_without_user_signature() {
+        let (env, client, user) = setup_test_contract();
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let schedule_id = client.create_autosave(
+            &user,
+            &1_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        assert_eq!(
+            client
+                .get_autosave(&schedule_id)
+                .unwrap()
+                .allowance_expiry_ledger,
+            ALLOWANCE_EXPIRY
+        );
+
+        // No authorizations are mocked from here on: the keeper signs nothing
+        env.set_auths(&[]);
+        let results = client.execute_due_autosaves(&None, &soroban_sdk::vec![&env, schedule_id]);
+        assert!(results.get(0).unwrap());
+
+        assert_eq!(client.get_flexi_balance(&user), 1_000);
+        assert_eq!(token.balance(&user), 999_000);
+        assert_eq!(token.allowance(&user, &client.address), 999_000);
+    }
+
+    #[test]
+    fn test_insufficient_or_expired_allowance_emits_event() {
+        let (env, client, user) = setup_test_contract();
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let schedule_id = client.create_autosave(
+            &user,
+            &1_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let ids = soroban_sdk::vec![&env, schedule_id];
+        let allowance_topics = (
+            Symbol::new(&env, "autosave_allowance"),
+            user.clone(),
+            schedule_id,
+        )
+            .into_val(&env);
+
+        token.approve(&user, &client.address, &999, &ALLOWANCE_EXPIRY);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert!(env
+            .events()
+            .all()
+            .iter()
+            .any(|(_, topics, _)| topics == allowance_topics));
+        assert_eq!(client.get_flexi_balance(&user), 0);
+
+        // The recorded expiry has passed even though the token still allows it
+        token.approve(&user, &client.address, &5_000, &(ALLOWANCE_EXPIRY + 100));
+        env.ledger()
+            .with_mut(|li| li.sequence_number = ALLOWANCE_EXPIRY + 1);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+
+        client.set_autosave_allowance_expiry(&user, &schedule_id, &(ALLOWANCE_EXPIRY + 100));
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(client.get_flexi_balance(&user), 1_000);
+    }
+
+    #[test]
+    fn test_failures_are_tracked_and_reset_on_success() {
+        let (env, client, user) = setup_test_contract();
+        let token = TokenClient::new(&env, &client.get_config().settlement_asset.unwrap());
+
+        let schedule_id = client.create_autosave(
+            &user,
+            &1_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let ids = soroban_sdk::vec![&env, schedule_id];
+
+        token.approve(&user, &client.address, &0, &ALLOWANCE_EXPIRY);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        let fail_topics = (Symbol::new(&env, "as_fail"), user.clone(), schedule_id).into_val(&env);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| *topics == fail_topics)
+            .unwrap();
+        let (code, failures): (u32, u32) = data.into_val(&env);
+        assert_eq!(code, SavingsError::InsufficientBalance as u32);
+        assert_eq!(failures, 1);
+
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.consecutive_failures, 1);
+        assert_eq!(schedule.last_failure_reason, Some(40));
+        assert_eq!(schedule.last_success_time, None);
+        assert_eq!(schedule.status, AutoSaveStatus::Active);
+
+        token.approve(&user, &client.address, &1_000, &ALLOWANCE_EXPIRY);
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.consecutive_failures, 0);
+        assert_eq!(schedule.last_success_time, Some(env.ledger().timestamp()));
+    }
+
+    #[test]
+    fn test_schedule_deactivates_after_max_failures() {
+        let (env, client, user) = setup_test_contract();
+        assert_eq!(client.get_autosave_max_failures(), 3);
+        client.set_autosave_max_failures(&2);
+
+        // A wallet that cannot cover the amount fails every run
+        let schedule_id = client.create_autosave(
+            &user,
+            &2_000_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let ids = soroban_sdk::vec![&env, schedule_id];
+
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(
+            client.get_autosave(&schedule_id).unwrap().status,
+            AutoSaveStatus::Active
+        );
+
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.consecutive_failures, 2);
+        assert_eq!(schedule.status, AutoSaveStatus::Deactivated);
+
+        // Inactive schedules are skipped without counting another failure
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(
+            client
+                .get_autosave(&schedule_id)
+                .unwrap()
+                .consecutive_failures,
+            2
+        );
+    }
+
+    /// Creates a daily 1000-unit Flexi schedule under `policy` and moves the
+    /// ledger 3.5 intervals past its start, leaving four slots due
+    fn late_schedule(
+        env: &Env,
+        client: &NesteraContractClient,
+        user: &Address,
+        policy: MissedIntervalPolicy,
+    ) -> u64 {
+        let start = env.ledger().timestamp();
+        let schedule_id = client.create_autosave(
+            user,
+            &1000,
+            &86400,
+            &start,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &policy,
+        );
+        env.ledger()
+            .with_mut(|li| li.timestamp = start + 3 * 86400 + 43200);
+        schedule_id
+    }
+
+    #[test]
+    fn test_single_policy_runs_one_interval_when_late() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let schedule_id = late_schedule(&env, &client, &user, MissedIntervalPolicy::Single);
+
+        assert_eq!(client.execute_autosave(&schedule_id), 1);
+        assert_eq!(client.get_flexi_balance(&user), 1000);
+
+        // The backlog stays due, one interval at a time
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.next_execution_time, start + 86400);
+    }
+
+    #[test]
+    fn test_skip_missed_policy_jumps_to_next_future_slot() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let schedule_id = late_schedule(&env, &client, &user, MissedIntervalPolicy::SkipMissed);
+
+        assert_eq!(client.execute_autosave(&schedule_id), 1);
+        assert_eq!(client.get_flexi_balance(&user), 1000);
+
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.next_execution_time, start + 4 * 86400);
+        assert!(schedule.next_execution_time > env.ledger().timestamp());
+    }
+
+    #[test]
+    fn test_catch_up_policy_deposits_missed_intervals_up_to_cap() {
+        let (env, client, user) = setup_test_contract();
+        let start = env.ledger().timestamp();
+        let schedule_id = late_schedule(&env, &client, &user, MissedIntervalPolicy::CatchUp(3));
+
+        // Four slots are due but the cap allows three in one call
+        assert_eq!(client.execute_autosave(&schedule_id), 3);
+        let exec_topics = (Symbol::new(&env, "as_exec"), user.clone(), schedule_id).into_val(&env);
+        assert!(env
+            .events()
+            .all()
+            .iter()
+            .any(|(_, topics, data)| topics == exec_topics
+                && <(u32, i128)>::try_from_val(&env, &data) == Ok((3, 3000))));
+        assert_eq!(client.get_flexi_balance(&user), 3000);
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.next_execution_time, start + 3 * 86400);
+
+        // The remaining slot is picked up by the next run
+        assert_eq!(client.execute_autosave(&schedule_id), 1);
+        assert_eq!(client.get_flexi_balance(&user), 4000);
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.next_execution_time, start + 4 * 86400);
+    }
+
+    #[test]
+    fn test_create_autosave_rejects_zero_catch_up_cap() {
+        let (env, client, user) = setup_test_contract();
+
+        let result = client.try_create_autosave(
+            &user,
+            &1000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::CatchUp(0),
+        );
+        assert_eq!(result, Err(Ok(SavingsError::InvalidPlanConfig)));
+    }
+
+    /// Charges a 10% protocol fee and registers a keeper, returning the
+    /// keeper and the settlement asset
+    fn setup_keeper(env: &Env, client: &NesteraContractClient) -> (Address, Address) {
+        client.set_protocol_fee_bps(&1_000);
+        let keeper = Address::generate(env);
+        client.register_keeper(&keeper);
+        (keeper, client.get_config().settlement_asset.unwrap())
+    }
+
+    fn flexi_schedule(env: &Env, client: &NesteraContractClient, user: &Address) -> u64 {
+        client.create_autosave(
+            user,
+            &1_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        )
+    }
+
+    #[test]
+    fn test_registered_keeper_earns_bps_tip_from_protocol_fees() {
+        let (env, client, user) = setup_test_contract();
+        let (keeper, asset) = setup_keeper(&env, &client);
+        client.set_keeper_tip(&KeeperTip::Bps(500), &1_000);
+        let schedule_id = flexi_schedule(&env, &client, &user);
+
+        let ids = soroban_sdk::vec![&env, schedule_id];
+        assert!(client
+            .execute_due_autosaves(&Some(keeper.clone()), &ids)
+            .get(0)
+            .unwrap());
+
+        // 5% of the 1000 executed, out of the 100 protocol fee it accrued
+        assert_eq!(TokenClient::new(&env, &asset).balance(&keeper), 50);
+        assert_eq!(client.get_asset_fee_balance(&asset).accrued, 50);
+        let earnings = client.get_keeper_earnings(&keeper);
+        assert_eq!(earnings.len(), 1);
+        assert_eq!(
+            earnings.get(0).unwrap(),
+            KeeperEarning { asset, amount: 50 }
+        );
+    }
+
+    #[test]
+    fn test_keeper_tips_are_capped_per_batch() {
+        let (env, client, user) = setup_test_contract();
+        let (keeper, asset) = setup_keeper(&env, &client);
+        client.set_keeper_tip(&KeeperTip::Flat(30), &40);
+
+        let ids = soroban_sdk::vec![
+            &env,
+            flexi_schedule(&env, &client, &user),
+            flexi_schedule(&env, &client, &user)
+        ];
+        client.execute_due_autosaves(&Some(keeper.clone()), &ids);
+        assert_eq!(TokenClient::new(&env, &asset).balance(&keeper), 40);
+
+        // The cap applies per batch, so the next one earns again
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.execute_due_autosaves(&Some(keeper.clone()), &ids);
+        assert_eq!(
+            client.get_keeper_earnings(&keeper).get(0).unwrap().amount,
+            80
+        );
+    }
+
+    #[test]
+    fn test_unregistered_keeper_and_unfunded_tips_pay_nothing() {
+        let (env, client, user) = setup_test_contract();
+        let (keeper, asset) = setup_keeper(&env, &client);
+        client.set_keeper_tip(&KeeperTip::Flat(500), &1_000);
+        let token = TokenClient::new(&env, &asset);
+
+        let outsider = Address::generate(&env);
+        let ids = soroban_sdk::vec![&env, flexi_schedule(&env, &client, &user)];
+        assert!(client
+            .execute_due_autosaves(&Some(outsider.clone()), &ids)
+            .get(0)
+            .unwrap());
+        assert_eq!(token.balance(&outsider), 0);
+        assert_eq!(client.get_keeper_earnings(&outsider).len(), 0);
+
+        // Tips are limited to the fees accrued: 100 from this run, 100 before
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.execute_due_autosaves(&Some(keeper.clone()), &ids);
+        assert_eq!(token.balance(&keeper), 200);
+        assert_eq!(client.get_asset_fee_balance(&asset).accrued, 0);
+
+        client.remove_keeper(&keeper);
+        assert!(!client.is_keeper(&keeper));
+    }
+
+    #[test]
+    fn test_set_keeper_tip_validates_config() {
+        let (_env, client, _user) = setup_test_contract();
+
+        assert_eq!(
+            client.try_set_keeper_tip(&KeeperTip::Bps(10_001), &100),
+            Err(Ok(SavingsError::InvalidFeeBps))
+        );
+        assert_eq!(
+            client.try_set_keeper_tip(&KeeperTip::Flat(-1), &100),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_keeper_tip(&KeeperTip::Flat(10), &-1),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_keeper_tip(&KeeperTip::Bps(500), &0),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        client.set_keeper_tip(&KeeperTip::Disabled, &0);
+        assert_eq!(client.get_keeper_tip().tip, KeeperTip::Disabled);
+    }
+
+    #[test]
+    fn test_pause_and_resume_autosave() {
+        let (env, client, user) = setup_test_contract();
+        let schedule_id = flexi_schedule(&env, &client, &user);
+        let ids = soroban_sdk::vec![&env, schedule_id];
+
+        client.pause_autosave(&user, &schedule_id);
+        assert_eq!(
+            client.get_user_autosaves(&user).get(0).unwrap().status,
+            AutoSaveStatus::Paused
+        );
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(
+            client.try_pause_autosave(&user, &schedule_id),
+            Err(Ok(SavingsError::InvalidPlanConfig))
+        );
+
+        // The next run is recomputed from the time of resuming
+        env.ledger().with_mut(|li| li.timestamp += 10 * 86400);
+        let next = client.resume_autosave(&user, &schedule_id);
+        assert_eq!(next, env.ledger().timestamp() + 86400);
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        assert!(client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(client.get_flexi_balance(&user), 1_000);
+
+        client.cancel_autosave(&user, &schedule_id);
+        assert_eq!(
+            client.try_resume_autosave(&user, &schedule_id),
+            Err(Ok(SavingsError::PlanCompleted))
+        );
+    }
+
+    #[test]
+    fn test_update_autosave_changes_amount_and_interval() {
+        let (env, client, user) = setup_test_contract();
+        let schedule_id = flexi_schedule(&env, &client, &user);
+
+        assert_eq!(
+            client.try_update_autosave(&user, &schedule_id, &0, &3600),
+            Err(Ok(SavingsError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_update_autosave(&user, &schedule_id, &500, &0),
+            Err(Ok(SavingsError::InvalidTimestamp))
+        );
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.try_update_autosave(&other, &schedule_id, &500, &3600),
+            Err(Ok(SavingsError::Unauthorized))
+        );
+
+        client.update_autosave(&user, &schedule_id, &500, &3600);
+        let update_topics =
+            (Symbol::new(&env, "as_update"), user.clone(), schedule_id).into_val(&env);
+        assert!(env
+            .events()
+            .all()
+            .iter()
+            .any(|(_, topics, _)| topics == update_topics));
+
+        assert_eq!(client.execute_autosave(&schedule_id), 1);
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.amount, 500);
+        assert_eq!(
+            schedule.next_execution_time,
+            env.ledger().timestamp() + 3600
+        );
+        assert_eq!(client.get_flexi_balance(&user), 500);
+    }
+
+    #[test]
+    fn test_deactivated_schedule_can_be_resumed() {
+        let (env, client, user) = setup_test_contract();
+        client.set_autosave_max_failures(&1);
+
+        let schedule_id = client.create_autosave(
+            &user,
+            &2_000_000,
+            &86400,
+            &env.ledger().timestamp(),
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        );
+        let ids = soroban_sdk::vec![&env, schedule_id];
+        assert!(!client.execute_due_autosaves(&None, &ids).get(0).unwrap());
+        assert_eq!(
+            client.get_autosave(&schedule_id).unwrap().status,
+            AutoSaveStatus::Deactivated
+        );
+
+        // Lowering the amount and resuming brings it back with a clean record
+        client.update_autosave(&user, &schedule_id, &1_000, &86400);
+        client.resume_autosave(&user, &schedule_id);
+        let schedule = client.get_autosave(&schedule_id).unwrap();
+        assert_eq!(schedule.status, AutoSaveStatus::Active);
+        assert_eq!(schedule.consecutive_failures, 0);
+    }
+
+    fn schedule_at(env: &Env, client: &NesteraContractClient, user: &Address, start: u64) -> u64 {
+        client.create_autosave(
+            user,
+            &1_000,
+            &86400,
+            &start,
+            &AutoSaveDestination::Flexi,
+            &ALLOWANCE_EXPIRY,
+            &MissedIntervalPolicy::Single,
+        )
+    }
+
+    #[test]
+    fn test_get_due_autosaves_returns_due_ids_in_order() {
+        let (env, client, user) = setup_test_contract();
+        env.ledger().with_mut(|li| li.timestamp = 100_000);
+
+        // Two share an hour bucket, created out of order
+        let late = schedule_at(&env, &client, &user, 100_000 + 1_800);
+        let early = schedule_at(&env, &client, &user, 100_000 + 600);
+        let earlier_hour = schedule_at(&env, &client, &user, 100_000 - 7_200);
+        let future = schedule_at(&env, &client, &user, 100_000 + 86_400);
+
+        assert_eq!(
+            client.get_due_autosaves(&100_000, &10),
+            soroban_sdk::vec![&env, earlier_hour]
+        );
+        assert_eq!(
+            client.get_due_autosaves(&(100_000 + 3_000), &10),
+            soroban_sdk::vec![&env, earlier_hour, early, late]
+        );
+        assert_eq!(
+            client.get_due_autosaves(&(100_000 + 3_000), &2),
+            soroban_sdk::vec![&env, earlier_hour, early]
+        );
+        assert_eq!(client.get_due_autosaves(&(100_000 + 86_400), &10).len(), 4);
+        assert!(client
+            .get_due_autosaves(&(100_000 + 86_400), &10)
+            .contains(future));
+    }
+
+    #[test]
+    fn test_due_index_follows_execute_cancel_and_pause() {
+        let (env, client, user) = setup_test_contract();
+        let now = env.ledger().timestamp();
+        let first = schedule_at(&env, &client, &user, now);
+        let second = schedule_at(&env, &client, &user, now);
+        let third = schedule_at(&env, &client, &user, now);
+        assert_eq!(client.get_due_autosaves(&now, &10).len(), 3);
+
+        // Executing moves a schedule to its next run
+        let ids = soroban_sdk::vec![&env, first];
+        client.execute_due_autosaves(&None, &ids);
+        assert_eq!(
+            client.get_due_autosaves(&now, &10),
+            soroban_sdk::vec![&env, second, third]
+        );
+        assert_eq!(
+            client.get_due_autosaves(&(now + 86400), &10),
+            soroban_sdk::vec![&env, second, third, first]
+        );
+
+        client.cancel_autosave(&user, &second);
+        client.pause_autosave(&user, &third);
+        assert_eq!(client.get_due_autosaves(&now, &10).len(), 0);
+
+        // Resuming re-indexes the schedule one interval from now
+        client.resume_autosave(&user, &third);
+        assert_eq!(
+            client.get_due_autosaves(&(now + 86400), &10),
+            soroban_sdk::vec![&env, first, third]
+        );
+    }
 }
@@ -2,10 +2,17 @@
 
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
     Address, BytesN, Env, String as SorobanString, Symbol,
 };
 
-use Nestera::{NesteraContract, NesteraContractClient};
+use Nestera::{
+    AutoSaveDestination, AutoSaveStatus, MissedIntervalPolicy, NesteraContract,
+    NesteraContractClient,
+};
+
+/// Expiration ledger of the allowances users grant for AutoSave pulls
+const ALLOWANCE_EXPIRY: u32 = 10_000;
 
 fn setup_env() -> (
     Env,
@@ -25,9 +32,26 @@ fn setup_env() -> (
     let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+    // Base lock tier so short test locks have a rate to be priced at
+    client.set_lock_rate(&admin, &0, &500);
+    let token_admin = StellarAssetClient::new(&env, &token);
+
     let user1 = Address::generate(&env);
     let user2 = Address::generate(&env);
     let user3 = Address::generate(&env);
+    for user in [&user1, &user2, &user3] {
+        token_admin.mint(user, &1_000_000_000);
+        TokenClient::new(&env, &token).approve(
+            user,
+            &contract_id,
+            &1_000_000_000,
+            &ALLOWANCE_EXPIRY,
+        );
+    }
 
     (env, client, admin, user1, user2, user3)
 }
@@ -155,7 +179,8 @@ fn test_goal_plan_full_flow() {
     let initial_deposit = 1000i128;
 
     // Create Goal plan
-    let goal_id = client.create_goal_save(&user1, &goal_name, &target_amount, &initial_deposit);
+    let goal_id =
+        client.create_goal_save(&user1, &goal_name, &target_amount, &initial_deposit, &None);
 
     // Verify goal created
     let goal = client.get_goal_save_detail(&goal_id);
@@ -202,7 +227,8 @@ fn test_goal_early_withdrawal_with_penalty() {
     let target_amount = 10000i128;
     let initial_deposit = 3000i128;
 
-    let goal_id = client.create_goal_save(&user1, &goal_name, &target_amount, &initial_deposit);
+    let goal_id =
+        client.create_goal_save(&user1, &goal_name, &target_amount, &initial_deposit, &None);
 
     // Break goal before completion
     let returned = client.break_goal_save(&user1, &goal_id);
@@ -307,7 +333,7 @@ fn test_group_save_full_flow() {
         &SorobanString::from_str(&env, "travel"),
         &9000, // target_amount
         &0,    // contribution_type
-        &1000, // contribution_amount
+        &2000, // contribution_amount
         &true, // is_public
         &env.ledger().timestamp(),
         &(env.ledger().timestamp() + 86400 * 30), // 30 days
@@ -373,7 +399,15 @@ fn test_autosave_full_flow() {
     let start_time = env.ledger().timestamp() + 100;
 
     // Create autosave schedule
-    let schedule_id = client.create_autosave(&user1, &amount, &interval, &start_time);
+    let schedule_id = client.create_autosave(
+        &user1,
+        &amount,
+        &interval,
+        &start_time,
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Verify schedule exists
     let schedule = client.get_autosave(&schedule_id);
@@ -381,7 +415,7 @@ fn test_autosave_full_flow() {
     let schedule = schedule.unwrap();
     assert_eq!(schedule.amount, amount);
     assert_eq!(schedule.interval_seconds, interval);
-    assert!(schedule.is_active);
+    assert_eq!(schedule.status, AutoSaveStatus::Active);
 
     // Advance ledger timestamp to trigger execution
     env.ledger().with_mut(|li| {
@@ -405,7 +439,15 @@ fn test_autosave_early_execution_fails() {
     client.deposit_flexi(&user1, &10000);
 
     let start_time = env.ledger().timestamp() + 1000;
-    let schedule_id = client.create_autosave(&user1, &500, &3600, &start_time);
+    let schedule_id = client.create_autosave(
+        &user1,
+        &500,
+        &3600,
+        &start_time,
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Try to execute before due time - should panic
     client.execute_autosave(&schedule_id);
@@ -421,9 +463,33 @@ fn test_autosave_batch_execution() {
     let current_time = env.ledger().timestamp();
 
     // Create multiple schedules with different timings
-    let schedule1 = client.create_autosave(&user1, &1000, &3600, &(current_time + 100));
-    let schedule2 = client.create_autosave(&user1, &2000, &7200, &(current_time + 200));
-    let _schedule3 = client.create_autosave(&user1, &500, &1800, &(current_time + 5000));
+    let schedule1 = client.create_autosave(
+        &user1,
+        &1000,
+        &3600,
+        &(current_time + 100),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
+    let schedule2 = client.create_autosave(
+        &user1,
+        &2000,
+        &7200,
+        &(current_time + 200),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
+    let _schedule3 = client.create_autosave(
+        &user1,
+        &500,
+        &1800,
+        &(current_time + 5000),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Advance time to make only schedule1 and schedule2 due
     env.ledger().with_mut(|li| {
@@ -446,14 +512,22 @@ fn test_autosave_cancel() {
     client.initialize_user(&user1);
     client.deposit_flexi(&user1, &10000);
 
-    let schedule_id = client.create_autosave(&user1, &1000, &3600, &env.ledger().timestamp());
+    let schedule_id = client.create_autosave(
+        &user1,
+        &1000,
+        &3600,
+        &env.ledger().timestamp(),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Cancel the schedule
     client.cancel_autosave(&user1, &schedule_id);
 
-    // Verify schedule is inactive
+    // Verify schedule is cancelled
     let schedule = client.get_autosave(&schedule_id).unwrap();
-    assert!(!schedule.is_active);
+    assert_eq!(schedule.status, AutoSaveStatus::Cancelled);
 }
 
 // =============================================================================
@@ -606,7 +680,15 @@ fn test_autosave_invalid_interval() {
     client.deposit_flexi(&user1, &10000);
 
     // Create autosave with zero interval - should panic
-    client.create_autosave(&user1, &1000, &0, &env.ledger().timestamp());
+    client.create_autosave(
+        &user1,
+        &1000,
+        &0,
+        &env.ledger().timestamp(),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 }
 
 // =============================================================================
@@ -629,10 +711,18 @@ fn test_multi_user_multi_plan_scenario() {
 
     // User1 creates multiple plans
     client.create_lock_save(&user1, &5000, &30);
-    client.create_goal_save(&user1, &Symbol::new(&env, "vacation"), &8000, &3000);
+    client.create_goal_save(&user1, &Symbol::new(&env, "vacation"), &8000, &3000, &None);
 
     // User2 creates autosave
-    let _autosave1 = client.create_autosave(&user2, &500, &3600, &env.ledger().timestamp());
+    let _autosave1 = client.create_autosave(
+        &user2,
+        &500,
+        &3600,
+        &env.ledger().timestamp(),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // User3 creates a group
     let group_id = client.create_group_save(
@@ -642,7 +732,7 @@ fn test_multi_user_multi_plan_scenario() {
         &SorobanString::from_str(&env, "general"),
         &15000,
         &0,
-        &1000,
+        &5000,
         &true,
         &env.ledger().timestamp(),
         &(env.ledger().timestamp() + 86400 * 30),
@@ -678,7 +768,8 @@ fn test_complete_user_journey() {
     assert_eq!(client.get_flexi_balance(&user1), 50000);
 
     // Step 2: Create a goal (this doesn't deduct from flexi balance)
-    let goal_id = client.create_goal_save(&user1, &Symbol::new(&env, "house"), &20000, &5000);
+    let goal_id =
+        client.create_goal_save(&user1, &Symbol::new(&env, "house"), &20000, &5000, &None);
     // Note: Goal creation transfers funds internally, flexi balance should be 45000
     let balance = client.get_flexi_balance(&user1);
     // Only verify it decreased, exact amount may vary based on implementation
@@ -697,7 +788,15 @@ fn test_complete_user_journey() {
     );
 
     // Step 4: Setup autosave
-    let autosave_id = client.create_autosave(&user1, &1000, &86400, &env.ledger().timestamp());
+    let autosave_id = client.create_autosave(
+        &user1,
+        &1000,
+        &86400,
+        &env.ledger().timestamp(),
+        &AutoSaveDestination::Flexi,
+        &ALLOWANCE_EXPIRY,
+        &MissedIntervalPolicy::Single,
+    );
 
     // Step 5: Contribute to goal
     client.deposit_to_goal_save(&user1, &goal_id, &10000);
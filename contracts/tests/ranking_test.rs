@@ -1,5 +1,5 @@
 #![cfg(test)]
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, BytesN, Env, Vec};
 use Nestera::{NesteraContract, NesteraContractClient};
 
 fn create_test_env() -> (Env, NesteraContractClient<'static>, Address, Vec<Address>) {
@@ -13,11 +13,18 @@ fn create_test_env() -> (Env, NesteraContractClient<'static>, Address, Vec<Addre
     let admin_pk = BytesN::from_array(&env, &[0u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+    let token_admin = StellarAssetClient::new(&env, &token);
+
     // Create multiple test users
     let mut users = soroban_sdk::Vec::new(&env);
     for _ in 0..10 {
         let user = Address::generate(&env);
         client.init_user(&user);
+        token_admin.mint(&user, &1_000_000_000);
         users.push_back(user);
     }
 
@@ -235,10 +242,17 @@ fn test_large_user_set_safety() {
     client.initialize(&admin, &admin_pk);
     setup_rewards_config(&client, &admin);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+    let token_admin = StellarAssetClient::new(&env, &token);
+
     // Create and fund many users
     for i in 0..50 {
         let user = Address::generate(&env);
         client.init_user(&user);
+        token_admin.mint(&user, &1_000_000_000);
         client.deposit_flexi(&user, &(((i + 1) * 100) as i128));
     }
 
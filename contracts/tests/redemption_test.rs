@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, BytesN, Env};
 use Nestera::{NesteraContract, NesteraContractClient};
 
 fn create_test_env() -> (Env, NesteraContractClient<'static>, Address, Address) {
@@ -14,8 +14,15 @@ fn create_test_env() -> (Env, NesteraContractClient<'static>, Address, Address)
     let admin_pk = BytesN::from_array(&env, &[0u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+    let token_admin = StellarAssetClient::new(&env, &token);
+
     let user = Address::generate(&env);
     client.init_user(&user);
+    token_admin.mint(&user, &1_000_000_000);
 
     (env, client, admin, user)
 }
@@ -2,6 +2,7 @@
 
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
     Address, BytesN, Env, Symbol,
 };
 use Nestera::{NesteraContract, NesteraContractClient};
@@ -18,8 +19,14 @@ fn test_rewards_full_lifecycle() {
     let admin_pk = BytesN::from_array(&env, &[0u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+
     let user = Address::generate(&env);
     client.init_user(&user);
+    StellarAssetClient::new(&env, &token).mint(&user, &1_000_000);
 
     // 1. Initialize config
     client.init_rewards_config(
@@ -67,7 +74,8 @@ fn test_rewards_full_lifecycle() {
     let initial_deposit = 100i128;
 
     env.ledger().with_mut(|li| li.timestamp += 70);
-    let goal_id = client.create_goal_save(&user, &goal_name, &target_amount, &initial_deposit);
+    let goal_id =
+        client.create_goal_save(&user, &goal_name, &target_amount, &initial_deposit, &None);
 
     // Points for creation deposit (100 * 10 = 1000 + 20% streak bonus = 1200)
     // Cumulative: 3200 + 1200 = 4400
@@ -130,8 +138,14 @@ fn test_rewards_invariants_and_abuse() {
     let admin_pk = BytesN::from_array(&env, &[1u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+
     let user = Address::generate(&env);
     client.init_user(&user);
+    StellarAssetClient::new(&env, &token).mint(&user, &1_000_000);
 
     client.init_rewards_config(&admin, &10, &0, &0, &0, &true, &0, &0, &1000, &10000);
 
@@ -3,6 +3,7 @@
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events},
+    token::StellarAssetClient,
     vec, Address, BytesN, Env, IntoVal, Symbol,
 };
 use Nestera::{
@@ -21,8 +22,15 @@ fn create_test_env() -> (Env, NesteraContractClient<'static>, Address, Address)
     let admin_pk = BytesN::from_array(&env, &[0u8; 32]);
     client.initialize(&admin, &admin_pk);
 
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_settlement_asset(&admin, &token);
+    let token_admin = StellarAssetClient::new(&env, &token);
+
     let user = Address::generate(&env);
     client.init_user(&user);
+    token_admin.mint(&user, &1_000_000_000);
 
     (env, client, admin, user)
 }